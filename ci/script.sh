@@ -8,7 +8,7 @@ cargo fmt -- --check
 
 # Clippy
 touch ./*/*/*.rs
-cargo clippy -- -W warnings
+cargo clippy --all-features -- -W warnings
 
 # Tests
-cargo test --workspace -- --test-threads=1
+cargo test --workspace --all-features -- --test-threads=1
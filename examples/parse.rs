@@ -5,8 +5,6 @@ use yasec::Yasec;
 type StdError = Box<dyn std::error::Error + Send + Sync + 'static>;
 type Result<T> = std::result::Result<T, StdError>;
 
-// TODO enums
-
 #[derive(Debug, PartialEq)]
 pub struct Point {
     x: i32,
@@ -30,6 +28,13 @@ impl Yasec for Point {
     }
 }
 
+#[derive(Yasec, Debug)]
+#[yasec(case_insensitive)]
+pub enum Mode {
+    Client,
+    Server,
+}
+
 #[allow(dead_code)]
 #[derive(Yasec, Debug)]
 pub struct DB {
@@ -67,6 +72,9 @@ pub struct Config {
     #[yasec(default = "true")]
     pub enabled: bool,
 
+    #[yasec(default = "server")]
+    pub mode: Mode,
+
     pub db1: DB,
     pub db2: DB,
 
@@ -0,0 +1,82 @@
+use super::context::Context;
+use super::source::Source;
+use super::traits::{format_usage_type, Yasec};
+use super::YasecError;
+use std::collections::HashMap;
+
+/// Reads variables from command line flags.
+///
+/// Flags are inferred from the configuration structure: a variable `DB_HOST` is set by
+/// `--db-host 127.0.0.1` or `--db-host=127.0.0.1`, aliases of the variable are accepted too. A
+/// boolean flag may go without a value, which
+/// means `true`. `--help` and `-h` return `YasecError::HelpRequested` with the usage of the
+/// structure.
+#[derive(Debug, Clone)]
+pub struct Args {
+    vars: HashMap<String, String>,
+}
+
+impl Args {
+    /// Parses flags of the `T` configuration structure. The program name must not be passed.
+    pub fn parse<T: Yasec>(
+        args: impl IntoIterator<Item = impl Into<String>>,
+    ) -> Result<Self, YasecError> {
+        let fields = T::usage_with_context(Context::new(T::root_prefix()))?;
+        let mut args = args.into_iter().map(Into::into).peekable();
+        let mut vars = HashMap::new();
+        while let Some(arg) = args.next() {
+            if arg == "--help" || arg == "-h" {
+                return Err(YasecError::HelpRequested(help::<T>()?));
+            }
+
+            let (flag, value) = match arg.split_once('=') {
+                Some((flag, value)) => (flag, Some(value.to_owned())),
+                None => (arg.as_str(), None),
+            };
+            // The value may be secret, so only the flag is reported.
+            let error = |reason: &str| YasecError::ArgumentError {
+                arg: flag.to_owned(),
+                reason: reason.to_owned(),
+            };
+            if !flag.starts_with("--") {
+                return Err(error("expected a flag like `--name value`"));
+            }
+            let field = fields
+                .iter()
+                .find(|c| c.var_names().iter().any(|name| flag_name(name) == flag))
+                .ok_or_else(|| error("unknown flag"))?;
+            let is_bool = matches!(format_usage_type(field).as_str(), "bool" | "Option<bool>");
+            let value = match value {
+                Some(value) => value,
+                None if is_bool && args.peek().is_none_or(|a| a.starts_with("--")) => {
+                    "true".to_owned()
+                }
+                None => args.next().ok_or_else(|| error("expected a value"))?,
+            };
+            vars.insert(field.infer_var_name(), value);
+        }
+        Ok(Self { vars })
+    }
+}
+
+impl Source for Args {
+    fn lookup(&self, name: &str) -> Result<Option<String>, YasecError> {
+        self.vars.lookup(name)
+    }
+
+    fn keys(&self) -> Vec<String> {
+        self.vars.keys().cloned().collect()
+    }
+}
+
+/// Returns the command line flag of a variable, e.g. `--db-host` for `DB_HOST`.
+pub fn flag_name(var_name: &str) -> String {
+    format!("--{}", var_name.to_lowercase().replace('_', "-"))
+}
+
+fn help<T: Yasec>() -> Result<String, YasecError> {
+    Ok(format!(
+        "Every variable can be set with a command line flag, e.g. `DB_HOST` with `--db-host`.\n\n{}",
+        T::usage()?
+    ))
+}
@@ -1,38 +1,163 @@
-pub struct Context {
+use super::report::{Report, Warning};
+use super::source::{Env, Source};
+use std::rc::Rc;
+
+#[derive(Clone)]
+pub struct Context<'a> {
+    source: &'a dyn Source,
     prefix: Vec<String>,
+    root_len: usize,
     var_name: String,
     var_type: String,
     default_var_value: Option<String>,
+    file_suffix: bool,
+    interpolation: bool,
+    condition: Option<String>,
+    separator: String,
+    aliases: Vec<String>,
+    deprecated: Option<String>,
+    deprecated_names: Vec<(String, String)>,
+    report: Option<&'a Report>,
+    secret: bool,
+    secret_vars: Option<Rc<[String]>>,
+    description: Option<String>,
+    constraints: Vec<String>,
 }
 
-impl Context {
+impl Context<'static> {
     pub fn new(prefix: impl AsRef<str>) -> Self {
+        Context::from_source(prefix, &Env)
+    }
+}
+
+impl<'a> Context<'a> {
+    pub fn from_source(prefix: impl AsRef<str>, source: &'a dyn Source) -> Self {
         let prefix = prefix.as_ref().to_owned();
         let mut ret = Self {
+            source,
             prefix: vec![],
+            root_len: 0,
             var_name: String::default(),
             var_type: String::default(),
             default_var_value: None,
+            file_suffix: false,
+            interpolation: false,
+            condition: None,
+            separator: "_".to_owned(),
+            aliases: vec![],
+            deprecated: None,
+            deprecated_names: vec![],
+            report: None,
+            secret: false,
+            secret_vars: None,
+            description: None,
+            constraints: vec![],
         };
         if !prefix.is_empty() {
             ret.prefix.push(prefix);
+            ret.root_len = 1;
         }
         ret
     }
 
     pub fn prefix(&self) -> String {
-        self.prefix.join("_")
+        self.prefix.join(&self.separator)
+    }
+
+    /// Sets the separator of path segments in variable names, `_` by default. E.g. with `__`
+    /// the `conn_timeout` field of `db` is read from `DB__CONN_TIMEOUT`.
+    pub fn with_separator(&self, separator: &str) -> Self {
+        let mut ret = self.clone();
+        ret.separator = separator.to_owned();
+        ret
+    }
+
+    /// Returns the path of the field in the configuration structure. The root prefix, e.g. set by
+    /// `#[yasec(prefix = "...")]` on the structure, isn't a part of it.
+    pub fn path(&self) -> &[String] {
+        &self.prefix[self.root_len..]
     }
 
     pub fn with_var_name(&self, var_name: &str, var_type: &str) -> Self {
-        Self {
-            prefix: self.prefix.clone(),
-            var_name: var_name.to_owned(),
-            var_type: var_type.to_owned(),
-            default_var_value: None,
+        let mut ret = self.clone();
+        ret.var_name = var_name.to_owned();
+        ret.var_type = var_type.to_owned();
+        ret.default_var_value = None;
+        ret.aliases = vec![];
+        ret.deprecated_names = vec![];
+        ret.description = None;
+        ret.constraints = vec![];
+        ret
+    }
+
+    /// Sets names of variables which are tried in order when the variable itself is not set.
+    pub fn with_aliases(&self, aliases: &[&str]) -> Self {
+        let mut ret = self.clone();
+        ret.aliases = aliases.iter().map(|alias| alias.to_string()).collect();
+        ret
+    }
+
+    /// Records a warning with the message when the variable with the name, e.g. one of aliases,
+    /// is used.
+    pub fn with_deprecated_name(&self, var_name: &str, message: &str) -> Self {
+        let mut ret = self.clone();
+        ret.deprecated_names
+            .push((var_name.to_owned(), message.to_owned()));
+        ret
+    }
+
+    /// Records a warning with the message when the variable is set. The setting applies to nested
+    /// fields as well.
+    pub fn with_deprecated(&self, message: &str) -> Self {
+        let mut ret = self.clone();
+        ret.deprecated = Some(message.to_owned());
+        ret
+    }
+
+    /// Collects warnings to the report.
+    pub fn with_report(&self, report: &'a Report) -> Self {
+        let mut ret = self.clone();
+        ret.report = Some(report);
+        ret
+    }
+
+    /// Records a warning if the variable, which has been read, is deprecated.
+    pub fn warn_if_deprecated(&self, var_name: &str) {
+        let message = self
+            .deprecated_names
+            .iter()
+            .find(|(name, _)| name == var_name)
+            .map(|(_, message)| message)
+            .or(self.deprecated.as_ref());
+        let warning = match message {
+            Some(message) => Warning {
+                var_name: var_name.to_owned(),
+                message: message.clone(),
+            },
+            None => return,
+        };
+        #[cfg(feature = "log")]
+        log::warn!("{}", warning);
+        #[cfg(feature = "tracing")]
+        tracing::warn!("{}", warning);
+        if let Some(report) = self.report {
+            report.push(warning);
         }
     }
 
+    /// Returns the variable name followed by its aliases.
+    pub fn var_names(&self) -> Vec<String> {
+        std::iter::once(self.infer_var_name())
+            .chain(self.aliases.iter().cloned())
+            .collect()
+    }
+
+    pub fn with_var_type(&self, var_type: &str) -> Self {
+        let mut ret = self.clone();
+        ret.var_type = var_type.to_owned();
+        ret
+    }
+
     pub fn infer_var_name(&self) -> String {
         match self.var_name.len() {
             0 => self.prefix(),
@@ -41,23 +166,122 @@ impl Context {
     }
 
     pub fn push_prefix(&self, val: String) -> Self {
-        let mut ret = Self {
-            prefix: self.prefix.clone(),
-            var_name: self.var_name.clone(),
-            var_type: self.var_type.clone(),
-            default_var_value: None,
-        };
+        let mut ret = self.clone();
+        ret.default_var_value = None;
         ret.prefix.push(val);
         ret
     }
 
     pub fn with_default_value(&self, val: &str) -> Self {
-        Self {
-            prefix: self.prefix.clone(),
-            var_name: self.var_name.clone(),
-            var_type: self.var_type.clone(),
-            default_var_value: Some(String::from(val)),
-        }
+        let mut ret = self.clone();
+        ret.default_var_value = Some(String::from(val));
+        ret
+    }
+
+    /// Allows reading a value from a file, which path is set by the variable with `_FILE` suffix,
+    /// when the variable itself is not set. E.g. `DB_PASSWORD_FILE=/run/secrets/db` provides
+    /// `DB_PASSWORD`. The setting applies to nested fields as well, so it can be enabled for the
+    /// whole structure:
+    ///
+    /// ```
+    /// # use yasec::*;
+    /// # #[derive(Yasec)]
+    /// # struct Config {
+    /// #     #[yasec(default = "")]
+    /// #     db_password: String,
+    /// # }
+    /// let config = Config::init_with_file_suffix().unwrap();
+    /// ```
+    pub fn with_file_suffix(&self) -> Self {
+        let mut ret = self.clone();
+        ret.file_suffix = true;
+        ret
+    }
+
+    pub fn is_file_suffix(&self) -> bool {
+        self.file_suffix
+    }
+
+    /// Expands references to other variables like `${HOME}` in the value and the default of the
+    /// variable. The setting applies to nested fields as well, except secret ones, whose values are
+    /// always taken as is.
+    pub fn with_interpolation(&self) -> Self {
+        let mut ret = self.clone();
+        ret.interpolation = true;
+        ret
+    }
+
+    pub fn is_interpolation(&self) -> bool {
+        self.interpolation
+    }
+
+    /// Marks the variable as secret: its value is redacted in errors and its default is hidden
+    /// in usage. The setting applies to nested fields as well.
+    pub fn with_secret(&self) -> Self {
+        let mut ret = self.clone();
+        ret.secret = true;
+        ret
+    }
+
+    pub fn is_secret(&self) -> bool {
+        self.secret
+    }
+
+    /// Sets names of secret variables of the whole configuration, which other variables can't
+    /// reference by interpolation.
+    pub fn with_secret_vars(&self, var_names: Vec<String>) -> Self {
+        let mut ret = self.clone();
+        ret.secret_vars = Some(var_names.into());
+        ret
+    }
+
+    /// Returns `true` if names of secret variables are set, see `with_secret_vars`.
+    pub fn has_secret_vars(&self) -> bool {
+        self.secret_vars.is_some()
+    }
+
+    pub fn is_secret_var(&self, var_name: &str) -> bool {
+        self.secret_vars
+            .as_ref()
+            .is_some_and(|names| names.iter().any(|name| name == var_name))
+    }
+
+    /// Marks the variable as used only under the condition, e.g. `STORAGE_KIND=s3` for variables
+    /// of a variant of a tagged enum. Conditions of nested enums are combined. It's shown by
+    /// `usage`.
+    pub fn with_condition(&self, condition: &str) -> Self {
+        let mut ret = self.clone();
+        ret.condition = match ret.condition {
+            Some(parent) => Some(format!("{}, {}", parent, condition)),
+            None => Some(condition.to_owned()),
+        };
+        ret
+    }
+
+    pub fn get_condition(&self) -> Option<String> {
+        self.condition.clone()
+    }
+
+    /// Sets the description of the variable shown by `usage`.
+    pub fn with_description(&self, description: &str) -> Self {
+        let mut ret = self.clone();
+        ret.description = Some(description.to_owned());
+        ret
+    }
+
+    pub fn get_description(&self) -> Option<String> {
+        self.description.clone()
+    }
+
+    /// Adds a constraint of the value, e.g. `1..=65535`, shown by `usage`.
+    pub fn with_constraint(&self, constraint: &str) -> Self {
+        let mut ret = self.clone();
+        ret.constraints.push(constraint.to_owned());
+        ret
+    }
+
+    pub fn get_constraints(&self) -> Vec<String> {
+        self.constraints.clone()
     }
 
     pub fn get_default_value(&self) -> Option<String> {
@@ -67,4 +291,8 @@ impl Context {
     pub fn get_var_type(&self) -> String {
         self.var_type.clone()
     }
+
+    pub fn source(&self) -> &'a dyn Source {
+        self.source
+    }
 }
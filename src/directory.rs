@@ -0,0 +1,76 @@
+use super::source::Source;
+use super::YasecError;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Reads variables from files of a directory: a file name is a variable name and file contents
+/// is its value. One trailing newline is trimmed.
+///
+/// That's the layout of Kubernetes ConfigMap and Secret volumes and of systemd
+/// `$CREDENTIALS_DIRECTORY`. Entries starting with `..`, like the `..data` symlink Kubernetes
+/// creates, and subdirectories are ignored. Files are read on every lookup, so updates of the
+/// mounted volume are visible without restart.
+#[derive(Debug, Clone)]
+pub struct Directory {
+    path: PathBuf,
+}
+
+impl Directory {
+    pub fn from_path(path: impl AsRef<Path>) -> Result<Self, YasecError> {
+        let path = path.as_ref();
+        match fs::metadata(path) {
+            Ok(meta) if meta.is_dir() => Ok(Self {
+                path: path.to_owned(),
+            }),
+            Ok(_) => Err(YasecError::ReadFileError {
+                path: path.display().to_string(),
+                source: io::Error::other("not a directory"),
+            }),
+            Err(e) => Err(YasecError::ReadFileError {
+                path: path.display().to_string(),
+                source: e,
+            }),
+        }
+    }
+
+    fn entry_path(&self, name: &str) -> Option<PathBuf> {
+        if name.is_empty() || name.starts_with("..") || name.contains(['/', '\\']) {
+            return None;
+        }
+        let path = self.path.join(name);
+        match fs::metadata(&path) {
+            Ok(meta) if meta.is_file() => Some(path),
+            _ => None,
+        }
+    }
+}
+
+impl Source for Directory {
+    fn lookup(&self, name: &str) -> Result<Option<String>, YasecError> {
+        let path = match self.entry_path(name) {
+            Some(path) => path,
+            None => return Ok(None),
+        };
+        let content = fs::read_to_string(&path).map_err(|e| YasecError::ReadFileError {
+            path: path.display().to_string(),
+            source: e,
+        })?;
+        let value = match content.strip_suffix('\n') {
+            Some(value) => value.strip_suffix('\r').unwrap_or(value),
+            None => &content,
+        };
+        Ok(Some(value.to_owned()))
+    }
+
+    fn keys(&self) -> Vec<String> {
+        let entries = match fs::read_dir(&self.path) {
+            Ok(entries) => entries,
+            Err(_) => return vec![],
+        };
+        entries
+            .filter_map(|entry| entry.ok()?.file_name().into_string().ok())
+            .filter(|name| self.entry_path(name).is_some())
+            .collect()
+    }
+}
@@ -0,0 +1,212 @@
+use super::source::{Source, Value};
+use super::YasecError;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Reads variables from a structured configuration file: TOML, YAML or JSON.
+///
+/// A field is found by the path of the field in the configuration structure, so `[db] port = 5432`
+/// provides the same field as `DB_PORT` does. Keys are matched case-insensitively. Arrays and
+/// tables are passed to `Vec<T>` and `HashMap<String, String>` fields as they are.
+///
+/// The document also provides flattened names like `DB_PORT`, so it can be stacked with other
+/// sources in `Layered`.
+#[derive(Debug, Clone)]
+pub struct Document {
+    root: Node,
+    vars: HashMap<String, String>,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) enum Node {
+    Scalar(String),
+    List(Vec<Node>),
+    Table(Vec<(String, Node)>),
+}
+
+impl Document {
+    pub(crate) fn new(root: Node) -> Self {
+        let mut vars = HashMap::new();
+        if let Node::Table(ref entries) = root {
+            flatten(&mut vars, "", entries);
+        }
+        Self { root, vars }
+    }
+
+    /// Parses a TOML file.
+    #[cfg(feature = "toml")]
+    pub fn from_toml_path(path: impl AsRef<Path>) -> Result<Self, YasecError> {
+        let (path, content) = read(path.as_ref())?;
+        let table = content
+            .parse::<toml::Table>()
+            .map_err(|e| YasecError::ParseFileError {
+                path,
+                reason: e.to_string(),
+            })?;
+        Ok(Self::new(from_toml(toml::Value::Table(table))))
+    }
+
+    /// Parses a YAML file.
+    #[cfg(feature = "yaml")]
+    pub fn from_yaml_path(path: impl AsRef<Path>) -> Result<Self, YasecError> {
+        let (path, content) = read(path.as_ref())?;
+        let value = serde_yaml::from_str::<serde_yaml::Value>(&content).map_err(|e| {
+            YasecError::ParseFileError {
+                path: path.clone(),
+                reason: e.to_string(),
+            }
+        })?;
+        Self::from_root(path, from_yaml(value))
+    }
+
+    /// Parses a JSON file.
+    #[cfg(feature = "json")]
+    pub fn from_json_path(path: impl AsRef<Path>) -> Result<Self, YasecError> {
+        let (path, content) = read(path.as_ref())?;
+        let value = serde_json::from_str::<serde_json::Value>(&content).map_err(|e| {
+            YasecError::ParseFileError {
+                path: path.clone(),
+                reason: e.to_string(),
+            }
+        })?;
+        Self::from_root(path, from_json(value))
+    }
+
+    #[cfg(any(feature = "yaml", feature = "json"))]
+    fn from_root(path: String, root: Option<Node>) -> Result<Self, YasecError> {
+        match root {
+            Some(root @ Node::Table(_)) => Ok(Self::new(root)),
+            None => Ok(Self::new(Node::Table(vec![]))),
+            Some(_) => Err(YasecError::ParseFileError {
+                path,
+                reason: "expected a mapping at the top level".to_owned(),
+            }),
+        }
+    }
+
+    fn find(&self, path: &[String]) -> Option<&Node> {
+        path.iter()
+            .try_fold(&self.root, |node, segment| match node {
+                Node::Table(entries) => entries
+                    .iter()
+                    .find(|(k, _v)| k.eq_ignore_ascii_case(segment))
+                    .map(|(_k, v)| v),
+                _ => None,
+            })
+    }
+}
+
+impl Source for Document {
+    fn lookup(&self, name: &str) -> Result<Option<String>, YasecError> {
+        Ok(self.vars.get(name).cloned())
+    }
+
+    fn lookup_value(&self, name: &str, path: &[String]) -> Result<Option<Value>, YasecError> {
+        match self.find(path) {
+            Some(node) if !path.is_empty() => Ok(Some(node.to_value())),
+            _ => Ok(self.lookup(name)?.map(Value::String)),
+        }
+    }
+
+    fn keys(&self) -> Vec<String> {
+        self.vars.keys().cloned().collect()
+    }
+}
+
+impl Node {
+    fn to_value(&self) -> Value {
+        match self {
+            Node::Scalar(v) => Value::String(v.clone()),
+            Node::List(items) => Value::List(items.iter().map(|item| item.to_string()).collect()),
+            Node::Table(entries) => Value::Map(
+                entries
+                    .iter()
+                    .map(|(k, v)| (k.clone(), v.to_string()))
+                    .collect(),
+            ),
+        }
+    }
+}
+
+impl std::fmt::Display for Node {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.to_value())
+    }
+}
+
+fn flatten(vars: &mut HashMap<String, String>, prefix: &str, entries: &[(String, Node)]) {
+    for (key, node) in entries {
+        let name = match prefix.is_empty() {
+            true => key.to_uppercase(),
+            false => format!("{}_{}", prefix, key.to_uppercase()),
+        };
+        if let Node::Table(ref entries) = node {
+            flatten(vars, &name, entries);
+        }
+        vars.insert(name, node.to_string());
+    }
+}
+
+fn read(path: &Path) -> Result<(String, String), YasecError> {
+    let content = std::fs::read_to_string(path).map_err(|e| YasecError::ReadFileError {
+        path: path.display().to_string(),
+        source: e,
+    })?;
+    Ok((path.display().to_string(), content))
+}
+
+#[cfg(feature = "toml")]
+fn from_toml(value: toml::Value) -> Node {
+    match value {
+        toml::Value::String(v) => Node::Scalar(v),
+        toml::Value::Array(items) => Node::List(items.into_iter().map(from_toml).collect()),
+        toml::Value::Table(table) => {
+            Node::Table(table.into_iter().map(|(k, v)| (k, from_toml(v))).collect())
+        }
+        v => Node::Scalar(v.to_string()),
+    }
+}
+
+#[cfg(feature = "yaml")]
+fn from_yaml(value: serde_yaml::Value) -> Option<Node> {
+    use serde_yaml::Value;
+    match value {
+        Value::Null => None,
+        Value::Bool(v) => Some(Node::Scalar(v.to_string())),
+        Value::Number(v) => Some(Node::Scalar(v.to_string())),
+        Value::String(v) => Some(Node::Scalar(v)),
+        Value::Sequence(items) => Some(Node::List(
+            items.into_iter().filter_map(from_yaml).collect(),
+        )),
+        Value::Mapping(mapping) => Some(Node::Table(
+            mapping
+                .into_iter()
+                .filter_map(|(k, v)| match (from_yaml(k), from_yaml(v)) {
+                    (Some(Node::Scalar(k)), Some(v)) => Some((k, v)),
+                    _ => None,
+                })
+                .collect(),
+        )),
+        Value::Tagged(tagged) => from_yaml(tagged.value),
+    }
+}
+
+#[cfg(feature = "json")]
+fn from_json(value: serde_json::Value) -> Option<Node> {
+    use serde_json::Value;
+    match value {
+        Value::Null => None,
+        Value::Bool(v) => Some(Node::Scalar(v.to_string())),
+        Value::Number(v) => Some(Node::Scalar(v.to_string())),
+        Value::String(v) => Some(Node::Scalar(v)),
+        Value::Array(items) => Some(Node::List(
+            items.into_iter().filter_map(from_json).collect(),
+        )),
+        Value::Object(object) => Some(Node::Table(
+            object
+                .into_iter()
+                .filter_map(|(k, v)| from_json(v).map(|v| (k, v)))
+                .collect(),
+        )),
+    }
+}
@@ -0,0 +1,164 @@
+use super::source::Source;
+use super::YasecError;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+/// Reads variables from a `.env` file.
+///
+/// Supported syntax:
+///
+/// - `KEY=value`, optionally prefixed with `export`;
+/// - blank lines and lines starting with `#`;
+/// - unquoted values, with a trailing comment after whitespace (`KEY=value # comment`);
+/// - single-quoted values, taken literally;
+/// - double-quoted values, which may span several lines and understand `\n`, `\r`, `\t`,
+///   `\"`, `\\` and `\$` escapes.
+///
+/// When a key is repeated the last value wins.
+#[derive(Debug, Clone)]
+pub struct Dotenv {
+    vars: HashMap<String, String>,
+}
+
+impl Dotenv {
+    pub fn from_path(path: impl AsRef<Path>) -> Result<Self, YasecError> {
+        let path = path.as_ref();
+        let content = fs::read_to_string(path).map_err(|e| YasecError::ReadFileError {
+            path: path.display().to_string(),
+            source: e,
+        })?;
+        let vars = parse(&path.display().to_string(), &content)?;
+        Ok(Self { vars })
+    }
+}
+
+impl Source for Dotenv {
+    fn lookup(&self, name: &str) -> Result<Option<String>, YasecError> {
+        self.vars.lookup(name)
+    }
+
+    fn keys(&self) -> Vec<String> {
+        self.vars.keys().cloned().collect()
+    }
+}
+
+fn parse(path: &str, content: &str) -> Result<HashMap<String, String>, YasecError> {
+    let mut vars = HashMap::new();
+    let mut lines = content.lines().enumerate();
+    while let Some((index, line)) = lines.next() {
+        let error = |reason: String| YasecError::DotenvError {
+            path: path.to_owned(),
+            line: index + 1,
+            reason,
+        };
+
+        let line = line.trim_start();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let line = match line.strip_prefix("export") {
+            Some(rest) if rest.starts_with(char::is_whitespace) => rest.trim_start(),
+            _ => line,
+        };
+        let (key, rest) = line
+            .split_once('=')
+            .ok_or_else(|| error("expected `KEY=VALUE`".to_owned()))?;
+        let key = key.trim_end();
+        if !is_valid_key(key) {
+            return Err(error(format!("invalid variable name `{}`", key)));
+        }
+
+        let rest = rest.trim_start();
+        let value = if let Some(rest) = rest.strip_prefix('\'') {
+            let (value, tail) = rest
+                .split_once('\'')
+                .ok_or_else(|| error("unterminated single-quoted value".to_owned()))?;
+            check_tail(tail).map_err(error)?;
+            value.to_owned()
+        } else if let Some(rest) = rest.strip_prefix('"') {
+            let mut raw = rest.to_owned();
+            let end = loop {
+                if let Some(end) = find_closing_quote(&raw) {
+                    break end;
+                }
+                match lines.next() {
+                    Some((_, next)) => {
+                        raw.push('\n');
+                        raw.push_str(next);
+                    }
+                    None => return Err(error("unterminated double-quoted value".to_owned())),
+                }
+            };
+            check_tail(&raw[end + 1..]).map_err(error)?;
+            unescape(&raw[..end])
+        } else {
+            let end = rest
+                .char_indices()
+                .find(|&(i, c)| c == '#' && rest[..i].ends_with(char::is_whitespace))
+                .map(|(i, _)| i)
+                .unwrap_or(rest.len());
+            rest[..end].trim_end().to_owned()
+        };
+
+        vars.insert(key.to_owned(), value);
+    }
+    Ok(vars)
+}
+
+fn is_valid_key(key: &str) -> bool {
+    let mut chars = key.chars();
+    match chars.next() {
+        Some(c) if c.is_ascii_alphabetic() || c == '_' => (),
+        _ => return false,
+    }
+    chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '.')
+}
+
+fn check_tail(tail: &str) -> Result<(), String> {
+    let tail = tail.trim_start();
+    match tail.is_empty() || tail.starts_with('#') {
+        true => Ok(()),
+        false => Err(format!(
+            "unexpected characters after quoted value `{}`",
+            tail
+        )),
+    }
+}
+
+fn find_closing_quote(raw: &str) -> Option<usize> {
+    let mut escaped = false;
+    for (i, c) in raw.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' => escaped = true,
+            '"' => return Some(i),
+            _ => (),
+        }
+    }
+    None
+}
+
+fn unescape(raw: &str) -> String {
+    let mut ret = String::with_capacity(raw.len());
+    let mut chars = raw.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            ret.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => ret.push('\n'),
+            Some('r') => ret.push('\r'),
+            Some('t') => ret.push('\t'),
+            Some(c @ ('"' | '\\' | '$')) => ret.push(c),
+            Some(c) => {
+                ret.push('\\');
+                ret.push(c);
+            }
+            None => ret.push('\\'),
+        }
+    }
+    ret
+}
@@ -1,3 +1,4 @@
+use super::secret::REDACTED;
 use thiserror::Error;
 
 use derivative::*;
@@ -21,10 +22,160 @@ pub enum YasecError {
         #[source]
         source: Box<dyn std::error::Error + Send + Sync + 'static>,
     },
+    #[error(
+        "Configuration from file failed. Variable: `{var_name}` read from file `{path}`, {source}"
+    )]
+    ParseFileVarError {
+        var_name: String,
+        path: String,
+        #[derivative(PartialEq = "ignore")]
+        #[source]
+        source: Box<dyn std::error::Error + Send + Sync + 'static>,
+    },
+    #[error(
+        "Configuration from file failed. Variable: `{var_name}` points to file `{path}`, {source}"
+    )]
+    ReadVarFileError {
+        var_name: String,
+        path: String,
+        #[derivative(PartialEq = "ignore")]
+        #[source]
+        source: std::io::Error,
+    },
     #[error(
         "Configuration from environment variables failed. Environment variable: {0} not present"
     )]
     EmptyVar(String),
+    #[error("Interpolation of variable `{var_name}` failed, {reason}")]
+    InterpolationError { var_name: String, reason: String },
+    #[error("Interpolation of variable `{}` failed, cycle `{}`", chain[0], chain.join(" -> "))]
+    InterpolationCycle { chain: Vec<String> },
+    #[error("Validation of variable `{var_name}` failed, {reason}")]
+    Validation { var_name: String, reason: String },
+    #[error("{}", format_struct_validation(prefix, source))]
+    StructValidation {
+        prefix: String,
+        #[derivative(PartialEq = "ignore")]
+        #[source]
+        source: Box<dyn std::error::Error + Send + Sync + 'static>,
+    },
+    #[error("Configuration failed. Variable: `{0}` is used by several fields")]
+    DuplicateVar(String),
+    #[error("Configuration failed. A field of type `{0}` is flattened, only structures can be flattened")]
+    FlattenedLeaf(String),
     #[error("Illegal value `{0}`")]
     IllegalVar(String),
+    #[error("Unknown value `{value}`, expected one of: {}", allowed.join(", "))]
+    UnknownVariant { value: String, allowed: Vec<String> },
+    #[error("{}", format_errors(.0))]
+    Multiple(Vec<YasecError>),
+    #[error("Failed to read file `{path}`, {source}")]
+    ReadFileError {
+        path: String,
+        #[derivative(PartialEq = "ignore")]
+        #[source]
+        source: std::io::Error,
+    },
+    #[error("Failed to parse file `{path}`, {reason}")]
+    ParseFileError { path: String, reason: String },
+    #[error("Invalid command line argument `{arg}`: {reason}")]
+    ArgumentError { arg: String, reason: String },
+    #[error("{0}")]
+    HelpRequested(String),
+    #[error("Failed to parse dotenv file `{path}` at line {line}: {reason}")]
+    DotenvError {
+        path: String,
+        line: usize,
+        reason: String,
+    },
+}
+
+impl YasecError {
+    /// Combines errors of several fields. A single error is returned as is, nested `Multiple`
+    /// errors are flattened.
+    pub fn from_errors(errors: Vec<YasecError>) -> Self {
+        let mut errors = errors
+            .into_iter()
+            .flat_map(|e| match e {
+                YasecError::Multiple(errors) => errors,
+                e => vec![e],
+            })
+            .collect::<Vec<_>>();
+        match errors.len() {
+            1 => errors.remove(0),
+            _ => YasecError::Multiple(errors),
+        }
+    }
+
+    /// Replaces the values of a secret variable in the error, so they don't leak to logs.
+    pub(crate) fn redact(self, values: &[&str]) -> Self {
+        let redact_str = |s: String| {
+            values
+                .iter()
+                .filter(|v| !v.is_empty())
+                .fold(s, |s, v| s.replace(v, REDACTED))
+        };
+        let redact_source = |e: Box<dyn std::error::Error + Send + Sync + 'static>| {
+            redact_str(e.to_string()).into()
+        };
+        match self {
+            YasecError::ParseEnvError {
+                var_name, source, ..
+            } => YasecError::ParseEnvError {
+                var_name,
+                var_value: REDACTED.to_owned(),
+                source: redact_source(source),
+            },
+            YasecError::ParseDefaultError {
+                var_name, source, ..
+            } => YasecError::ParseDefaultError {
+                var_name,
+                var_value: REDACTED.to_owned(),
+                source: redact_source(source),
+            },
+            YasecError::ParseFileVarError {
+                var_name,
+                path,
+                source,
+            } => YasecError::ParseFileVarError {
+                var_name,
+                path,
+                source: redact_source(source),
+            },
+            YasecError::Multiple(errors) => {
+                YasecError::Multiple(errors.into_iter().map(|e| e.redact(values)).collect())
+            }
+            e => e,
+        }
+    }
+
+    /// Returns every error this one consists of.
+    pub fn errors(&self) -> Vec<&YasecError> {
+        match self {
+            YasecError::Multiple(errors) => errors.iter().collect(),
+            e => vec![e],
+        }
+    }
+}
+
+fn format_struct_validation(prefix: &str, source: &dyn std::fmt::Display) -> String {
+    match prefix {
+        "" => format!("Validation of the configuration failed, {}", source),
+        prefix => format!(
+            "Validation of variables with prefix `{}` failed, {}",
+            prefix, source
+        ),
+    }
+}
+
+fn format_errors(errors: &[YasecError]) -> String {
+    let lines = errors
+        .iter()
+        .map(|e| format!("  - {}", e))
+        .collect::<Vec<_>>();
+    format!(
+        "Configuration failed with {} errors:\n{}",
+        errors.len(),
+        lines.join("\n")
+    )
 }
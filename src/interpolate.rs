@@ -0,0 +1,133 @@
+use super::context::Context;
+use super::YasecError;
+
+/// Expands references to other variables in a value of the `var_name` variable.
+///
+/// Supported expressions:
+///
+/// - `${VAR}` - value of `VAR` or an empty string if it's not set;
+/// - `${VAR:-fallback}` - `fallback` if `VAR` is not set or empty;
+/// - `${VAR-fallback}` - `fallback` if `VAR` is not set;
+/// - `${VAR:?message}` - an error with `message` if `VAR` is not set or empty;
+/// - `${VAR?message}` - an error with `message` if `VAR` is not set;
+/// - `$$` - a literal `$`.
+///
+/// Values of referenced variables and fallbacks are expanded as well. A `$` which isn't followed
+/// by `{` or `$` is kept as is. Secret variables of the context can't be referenced, so their
+/// values don't leak to other variables.
+pub(crate) fn interpolate(
+    context: &Context,
+    var_name: &str,
+    value: &str,
+) -> Result<String, YasecError> {
+    expand(context, value, &mut vec![var_name.to_owned()])
+}
+
+fn expand(context: &Context, value: &str, stack: &mut Vec<String>) -> Result<String, YasecError> {
+    let mut ret = String::with_capacity(value.len());
+    let mut rest = value;
+    while let Some(pos) = rest.find('$') {
+        ret.push_str(&rest[..pos]);
+        rest = &rest[pos..];
+        if let Some(tail) = rest.strip_prefix("$$") {
+            ret.push('$');
+            rest = tail;
+        } else if let Some(tail) = rest.strip_prefix("${") {
+            let end = find_closing_brace(tail)
+                .ok_or_else(|| error(stack, format!("unterminated expression `{}`", rest)))?;
+            ret.push_str(&substitute(context, &tail[..end], stack)?);
+            rest = &tail[end + 1..];
+        } else {
+            ret.push('$');
+            rest = &rest[1..];
+        }
+    }
+    ret.push_str(rest);
+    Ok(ret)
+}
+
+fn find_closing_brace(value: &str) -> Option<usize> {
+    let bytes = value.as_bytes();
+    let mut depth = 0;
+    let mut i = 0;
+    while i < bytes.len() {
+        match (bytes[i], bytes.get(i + 1)) {
+            (b'$', Some(b'$')) => i += 1,
+            (b'$', Some(b'{')) => {
+                depth += 1;
+                i += 1;
+            }
+            (b'}', _) if depth == 0 => return Some(i),
+            (b'}', _) => depth -= 1,
+            _ => (),
+        }
+        i += 1;
+    }
+    None
+}
+
+fn substitute(
+    context: &Context,
+    expr: &str,
+    stack: &mut Vec<String>,
+) -> Result<String, YasecError> {
+    let name_end = expr
+        .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+        .unwrap_or(expr.len());
+    let (name, op) = expr.split_at(name_end);
+    let invalid = |stack: &[String]| error(stack, format!("invalid expression `${{{}}}`", expr));
+    if name.is_empty() {
+        return Err(invalid(stack));
+    }
+
+    let value = resolve(context, name, stack)?;
+    let set = value.is_some();
+    let non_empty = value.as_ref().is_some_and(|v| !v.is_empty());
+    let (op, arg) = ["", ":-", "-", ":?", "?"]
+        .iter()
+        .rev()
+        .find_map(|prefix| op.strip_prefix(prefix).map(|arg| (*prefix, arg)))
+        .ok_or_else(|| invalid(stack))?;
+    match op {
+        "" if arg.is_empty() => Ok(value.unwrap_or_default()),
+        ":-" if !non_empty => expand(context, arg, stack),
+        "-" if !set => expand(context, arg, stack),
+        ":?" if !non_empty => Err(error(stack, format!("`{}` {}", name, arg))),
+        "?" if !set => Err(error(stack, format!("`{}` {}", name, arg))),
+        "" => Err(invalid(stack)),
+        _ => Ok(value.unwrap_or_default()),
+    }
+}
+
+fn resolve(
+    context: &Context,
+    name: &str,
+    stack: &mut Vec<String>,
+) -> Result<Option<String>, YasecError> {
+    if context.is_secret_var(name) {
+        return Err(error(
+            stack,
+            format!("`{}` is secret and can't be referenced", name),
+        ));
+    }
+    let value = match context.source().lookup(name)? {
+        Some(value) => value,
+        None => return Ok(None),
+    };
+    if stack.iter().any(|n| n == name) {
+        let mut chain = stack.clone();
+        chain.push(name.to_owned());
+        return Err(YasecError::InterpolationCycle { chain });
+    }
+    stack.push(name.to_owned());
+    let ret = expand(context, &value, stack);
+    stack.pop();
+    ret.map(Some)
+}
+
+fn error(stack: &[String], reason: String) -> YasecError {
+    YasecError::InterpolationError {
+        var_name: stack[0].clone(),
+        reason,
+    }
+}
@@ -0,0 +1,85 @@
+use super::source::{Source, Value};
+use super::YasecError;
+
+/// Stacks several sources on top of each other.
+///
+/// A variable is resolved from the top layer down and the first layer that has the variable
+/// wins. `layer` puts a source on top of the stack, `underlay` puts it at the bottom, so the
+/// resolution order is defined by how the stack is built:
+///
+/// ```
+/// use yasec::*;
+/// use std::collections::HashMap;
+///
+/// #[derive(Yasec)]
+/// struct Config {
+///     host: String,
+///     port: u16,
+/// }
+///
+/// let mut defaults = HashMap::new();
+/// defaults.insert("HOST".to_owned(), "localhost".to_owned());
+/// defaults.insert("PORT".to_owned(), "80".to_owned());
+///
+/// let mut overrides = HashMap::new();
+/// overrides.insert("PORT".to_owned(), "8080".to_owned());
+///
+/// let source = Layered::new().layer(defaults).layer(overrides);
+/// let config = Config::init_from(&source).unwrap();
+/// assert_eq!(config.host, "localhost");
+/// assert_eq!(config.port, 8080);
+/// ```
+#[derive(Default)]
+pub struct Layered<'a> {
+    // The top layer is the last one.
+    layers: Vec<Box<dyn Source + 'a>>,
+}
+
+impl<'a> Layered<'a> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Puts the source on top of the stack. It takes precedence over all added layers.
+    pub fn layer(mut self, source: impl Source + 'a) -> Self {
+        self.layers.push(Box::new(source));
+        self
+    }
+
+    /// Puts the source at the bottom of the stack. All added layers take precedence over it.
+    pub fn underlay(mut self, source: impl Source + 'a) -> Self {
+        self.layers.insert(0, Box::new(source));
+        self
+    }
+}
+
+impl<'a> Source for Layered<'a> {
+    fn lookup(&self, name: &str) -> Result<Option<String>, YasecError> {
+        for layer in self.layers.iter().rev() {
+            if let Some(value) = layer.lookup(name)? {
+                return Ok(Some(value));
+            }
+        }
+        Ok(None)
+    }
+
+    fn lookup_value(&self, name: &str, path: &[String]) -> Result<Option<Value>, YasecError> {
+        for layer in self.layers.iter().rev() {
+            if let Some(value) = layer.lookup_value(name, path)? {
+                return Ok(Some(value));
+            }
+        }
+        Ok(None)
+    }
+
+    fn keys(&self) -> Vec<String> {
+        let mut keys = self
+            .layers
+            .iter()
+            .flat_map(|layer| layer.keys())
+            .collect::<Vec<_>>();
+        keys.sort();
+        keys.dedup();
+        keys
+    }
+}
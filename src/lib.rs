@@ -34,15 +34,55 @@
 //! }
 //! ```
 //!
+//! Variables are read from the process environment by default. Any other `Source`, like an
+//! in-memory map, can be passed to `init_from`:
+//!
+//! ```
+//! use yasec::*;
+//! use std::collections::HashMap;
+//!
+//! #[derive(Yasec)]
+//! struct Config {
+//!     db_host: String,
+//! }
+//!
+//! let mut vars = HashMap::new();
+//! vars.insert("DB_HOST".to_owned(), "localhost".to_owned());
+//!
+//! let config = Config::init_from(&vars).unwrap();
+//! assert_eq!(config.db_host, "localhost");
+//! ```
+//!
 //! The library uses `std::str::FromStr` trait to convert environment variables into custom
 //! data type. So, if your data type does not implement `std::str::FromStr` the program
 //! will not compile.
 
+mod args;
 mod context;
+mod directory;
+#[cfg(any(feature = "toml", feature = "yaml", feature = "json"))]
+mod document;
+mod dotenv;
 mod error;
+mod interpolate;
+mod layered;
+mod report;
+mod secret;
+mod source;
 mod traits;
+mod validate;
 
+pub use args::*;
 pub use context::*;
+pub use directory::*;
+#[cfg(any(feature = "toml", feature = "yaml", feature = "json"))]
+pub use document::*;
+pub use dotenv::*;
 pub use error::*;
+pub use layered::*;
+pub use report::*;
+pub use secret::*;
+pub use source::*;
 pub use traits::*;
+pub use validate::*;
 pub use yasec_derive::*;
@@ -0,0 +1,44 @@
+use std::cell::RefCell;
+use std::fmt;
+
+/// A warning about the configuration, e.g. a deprecated variable which is set.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Warning {
+    pub var_name: String,
+    pub message: String,
+}
+
+impl fmt::Display for Warning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Variable `{}` is deprecated, {}",
+            self.var_name, self.message
+        )
+    }
+}
+
+/// Collects warnings while a structure is initialized, see `Yasec::init_with_report` and
+/// `Context::with_report`. Warnings are also logged with `log` or `tracing` features.
+#[derive(Debug, Default)]
+pub struct Report {
+    warnings: RefCell<Vec<Warning>>,
+}
+
+impl Report {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn warnings(&self) -> Vec<Warning> {
+        self.warnings.borrow().clone()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.warnings.borrow().is_empty()
+    }
+
+    pub(crate) fn push(&self, warning: Warning) {
+        self.warnings.borrow_mut().push(warning);
+    }
+}
@@ -0,0 +1,74 @@
+use super::context::Context;
+use super::source::Value;
+use super::traits::Yasec;
+use super::validate::Length;
+use super::YasecError;
+use std::fmt;
+use zeroize::Zeroize;
+
+/// Placeholder shown instead of a secret value.
+pub const REDACTED: &str = "***";
+
+/// A secret value, like a password or a token. It's loaded as a field with `#[yasec(secret)]`,
+/// so the value never appears in errors and usage. `Debug` doesn't show it either, and the value
+/// is zeroized on drop.
+pub struct Secret<T: Zeroize>(T);
+
+impl<T: Zeroize> Secret<T> {
+    pub fn new(value: T) -> Self {
+        Self(value)
+    }
+
+    /// Returns the secret value.
+    pub fn expose(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T: Zeroize> Drop for Secret<T> {
+    fn drop(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+impl<T: Zeroize> fmt::Debug for Secret<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Secret({})", REDACTED)
+    }
+}
+
+impl<T: Zeroize + Clone> Clone for Secret<T> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<T: Zeroize> From<T> for Secret<T> {
+    fn from(value: T) -> Self {
+        Self(value)
+    }
+}
+
+impl<T: Zeroize + Length> Length for Secret<T> {
+    fn length(&self) -> usize {
+        self.0.length()
+    }
+}
+
+impl<T: Yasec + Zeroize> Yasec for Secret<T> {
+    fn with_context(context: Context) -> Result<Self, YasecError> {
+        T::with_context(context.with_secret()).map(Self)
+    }
+
+    fn parse(val: &str) -> Result<Self, Box<dyn std::error::Error + Send + Sync + 'static>> {
+        T::parse(val).map(Self)
+    }
+
+    fn parse_value(val: Value) -> Result<Self, Box<dyn std::error::Error + Send + Sync + 'static>> {
+        T::parse_value(val).map(Self)
+    }
+
+    fn usage_with_context(context: Context) -> Result<Vec<Context>, YasecError> {
+        T::usage_with_context(context.with_secret())
+    }
+}
@@ -0,0 +1,106 @@
+use super::YasecError;
+use std::collections::{BTreeMap, HashMap};
+use std::env;
+use std::fmt;
+
+/// A value provided by a source.
+///
+/// Flat sources like the process environment provide strings only. Structured documents also
+/// provide lists and maps, so `Vec<T>` and `HashMap<String, String>` fields don't depend on
+/// comma splitting.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    String(String),
+    List(Vec<String>),
+    Map(Vec<(String, String)>),
+}
+
+/// Formats the value the way it would be written in an environment variable.
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Value::String(v) => write!(f, "{}", v),
+            Value::List(items) => write!(f, "{}", items.join(",")),
+            Value::Map(pairs) => {
+                let pairs = pairs
+                    .iter()
+                    .map(|(k, v)| format!("{}={}", k, v))
+                    .collect::<Vec<_>>();
+                write!(f, "{}", pairs.join(","))
+            }
+        }
+    }
+}
+
+/// Provides values of configuration variables by their names.
+///
+/// `Yasec::init` reads the process environment through `Env`. Any other implementation can be
+/// passed to `Yasec::init_from` to load the same structure without touching global state.
+pub trait Source {
+    /// Returns a value of the variable or `None` if the source doesn't have it.
+    fn lookup(&self, name: &str) -> Result<Option<String>, YasecError>;
+
+    /// Returns a value of the variable. `path` is the path of the field in the configuration
+    /// structure, e.g. `["DB", "PORT"]` for `DB_PORT`; structured sources use it to find nested
+    /// keys. By default calls `lookup`.
+    fn lookup_value(&self, name: &str, _path: &[String]) -> Result<Option<Value>, YasecError> {
+        Ok(self.lookup(name)?.map(Value::String))
+    }
+
+    /// Returns names of all variables the source has.
+    fn keys(&self) -> Vec<String>;
+}
+
+impl<S: Source + ?Sized> Source for &S {
+    fn lookup(&self, name: &str) -> Result<Option<String>, YasecError> {
+        (**self).lookup(name)
+    }
+
+    fn lookup_value(&self, name: &str, path: &[String]) -> Result<Option<Value>, YasecError> {
+        (**self).lookup_value(name, path)
+    }
+
+    fn keys(&self) -> Vec<String> {
+        (**self).keys()
+    }
+}
+
+/// Reads variables from the process environment.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Env;
+
+impl Source for Env {
+    fn lookup(&self, name: &str) -> Result<Option<String>, YasecError> {
+        match env::var(name) {
+            Ok(value) => Ok(Some(value)),
+            Err(env::VarError::NotPresent) => Ok(None),
+            Err(env::VarError::NotUnicode(_)) => Err(YasecError::IllegalVar(name.to_owned())),
+        }
+    }
+
+    fn keys(&self) -> Vec<String> {
+        env::vars_os()
+            .filter_map(|(k, _v)| k.into_string().ok())
+            .collect()
+    }
+}
+
+impl Source for HashMap<String, String> {
+    fn lookup(&self, name: &str) -> Result<Option<String>, YasecError> {
+        Ok(self.get(name).cloned())
+    }
+
+    fn keys(&self) -> Vec<String> {
+        HashMap::keys(self).cloned().collect()
+    }
+}
+
+impl Source for BTreeMap<String, String> {
+    fn lookup(&self, name: &str) -> Result<Option<String>, YasecError> {
+        Ok(self.get(name).cloned())
+    }
+
+    fn keys(&self) -> Vec<String> {
+        BTreeMap::keys(self).cloned().collect()
+    }
+}
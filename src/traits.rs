@@ -1,10 +1,18 @@
+use super::args::Args;
 use super::context::Context;
+use super::dotenv::Dotenv;
+use super::interpolate::interpolate;
+use super::layered::Layered;
+use super::report::Report;
+use super::secret::REDACTED;
+use super::source::{Env, Source, Value};
 use super::YasecError;
 use bytesize::ByteSize;
 use humantime::Duration;
 use regex::Regex;
-use std::collections::HashMap;
-use std::env;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::Path;
 
 type StdError = Box<dyn std::error::Error + Send + Sync + 'static>;
 
@@ -15,7 +23,16 @@ pub trait Yasec {
     where
         Self: Sized,
     {
-        Self::with_prefix("")
+        Self::with_prefix(Self::root_prefix())
+    }
+
+    /// Prefix of variables used by `init`, `init_from` and `usage`. It's set by
+    /// `#[yasec(prefix = "...")]` on a structure.
+    fn root_prefix() -> &'static str
+    where
+        Self: Sized,
+    {
+        ""
     }
 
     fn with_prefix(prefix: impl AsRef<str>) -> Result<Self, YasecError>
@@ -25,7 +42,82 @@ pub trait Yasec {
         Self::with_context(Context::new(prefix))
     }
 
-    /// Initialize structure from environment variable from the passed context.
+    /// Creates empty context over the passed source and calls `with_context`.
+    fn init_from(source: &dyn Source) -> Result<Self, YasecError>
+    where
+        Self: Sized,
+    {
+        Self::with_prefix_from(Self::root_prefix(), source)
+    }
+
+    fn with_prefix_from(prefix: impl AsRef<str>, source: &dyn Source) -> Result<Self, YasecError>
+    where
+        Self: Sized,
+    {
+        Self::with_context(Context::from_source(prefix, source))
+    }
+
+    /// Same as `init` but every variable can be read from a file, which path is set by the
+    /// variable with `_FILE` suffix, see `Context::with_file_suffix`.
+    fn init_with_file_suffix() -> Result<Self, YasecError>
+    where
+        Self: Sized,
+    {
+        Self::init_from_with_file_suffix(&Env)
+    }
+
+    /// Same as `init_from` but every variable can be read from a file, which path is set by the
+    /// variable with `_FILE` suffix.
+    fn init_from_with_file_suffix(source: &dyn Source) -> Result<Self, YasecError>
+    where
+        Self: Sized,
+    {
+        Self::with_context(Context::from_source(Self::root_prefix(), source).with_file_suffix())
+    }
+
+    /// Same as `init` but also returns warnings, e.g. about deprecated variables which are set.
+    fn init_with_report() -> Result<(Self, Report), YasecError>
+    where
+        Self: Sized,
+    {
+        let report = Report::new();
+        let value = Self::with_context(Context::new(Self::root_prefix()).with_report(&report))?;
+        Ok((value, report))
+    }
+
+    /// Initialize structure from the process environment and a `.env` file. Variables of the
+    /// process environment take precedence over the file.
+    fn init_with_dotenv(path: impl AsRef<Path>) -> Result<Self, YasecError>
+    where
+        Self: Sized,
+    {
+        let dotenv = Dotenv::from_path(path)?;
+        Self::init_from(&Layered::new().layer(dotenv).layer(Env))
+    }
+
+    /// Same as `init_with_dotenv` but variables of the file take precedence over the process
+    /// environment.
+    fn init_with_dotenv_overlay(path: impl AsRef<Path>) -> Result<Self, YasecError>
+    where
+        Self: Sized,
+    {
+        let dotenv = Dotenv::from_path(path)?;
+        Self::init_from(&Layered::new().layer(Env).layer(dotenv))
+    }
+
+    /// Initialize structure from the process environment overridden by command line flags, see
+    /// `Args`. The program name must not be passed.
+    fn init_with_args(args: impl IntoIterator<Item = impl Into<String>>) -> Result<Self, YasecError>
+    where
+        Self: Sized,
+    {
+        let args = Args::parse::<Self>(args)?;
+        Self::init_from(&Layered::new().layer(Env).layer(args))
+    }
+
+    /// Initialize structure from variables of the context source.
+    /// If the context enables interpolation, references to other variables like `${HOME}` in
+    /// values and defaults are expanded first, `$$` stands for a literal `$`.
     /// By default calls `parse` method. It works for a basic type like number or string.
     /// The method is redefined for a sctructure with `#[derive(Yasec)`. In that case
     /// the method pick every field type and calls the method for the type.
@@ -33,22 +125,59 @@ pub trait Yasec {
     where
         Self: Sized,
     {
-        let env_var_name = context.infer_var_name();
-        match env::var(&env_var_name) {
-            Ok(ref value) => Self::parse(value).map_err(|e| YasecError::ParseEnvError {
-                var_name: env_var_name,
-                var_value: value.to_owned(),
-                source: e,
-            }),
-            Err(e) => match context.get_default_value() {
-                Some(default) => Self::parse(&default).map_err(|e| YasecError::ParseDefaultError {
-                    var_name: env_var_name,
-                    var_value: default.to_owned(),
-                    source: e,
-                }),
-                None => match e {
-                    env::VarError::NotPresent => Err(YasecError::EmptyVar(env_var_name)),
-                    env::VarError::NotUnicode(_) => Err(YasecError::IllegalVar(env_var_name)),
+        check_var_name(&context)?;
+        let var_names = context.var_names();
+        let mut env_var_name = context.infer_var_name();
+        let mut found = Ok(None);
+        for name in var_names.iter() {
+            found = context.source().lookup_value(name, context.path());
+            if !matches!(found, Ok(None)) {
+                env_var_name = name.clone();
+                break;
+            }
+        }
+        if let Ok(None) = found {
+            if let Some(result) = parse_var_file(&context) {
+                return result;
+            }
+        }
+        match found {
+            Ok(Some(value)) => {
+                context.warn_if_deprecated(&env_var_name);
+                let value = match value {
+                    Value::String(v) if is_interpolated(&context) => {
+                        Value::String(interpolate(&context, &env_var_name, &v)?)
+                    }
+                    v => v,
+                };
+                let var_value = value.to_string();
+                Self::parse_value(value).map_err(|e| {
+                    let e = YasecError::ParseEnvError {
+                        var_name: env_var_name,
+                        var_value: var_value.clone(),
+                        source: e,
+                    };
+                    redact(&context, e, &[&var_value])
+                })
+            }
+            found => match context.get_default_value() {
+                Some(default) => {
+                    let default = match is_interpolated(&context) {
+                        true => interpolate(&context, &env_var_name, &default)?,
+                        false => default,
+                    };
+                    Self::parse(&default).map_err(|e| {
+                        let e = YasecError::ParseDefaultError {
+                            var_name: env_var_name,
+                            var_value: default.to_owned(),
+                            source: e,
+                        };
+                        redact(&context, e, &[&default])
+                    })
+                }
+                None => match found {
+                    Err(e) => Err(e),
+                    _ => Err(YasecError::EmptyVar(var_names.join(" or "))),
                 },
             },
         }
@@ -63,42 +192,189 @@ pub trait Yasec {
         Err(Box::new(YasecError::IllegalVar("".to_owned())))
     }
 
+    /// Parses a value provided by a source. By default formats the value as an environment
+    /// variable and calls `parse`. It should be implemented if a type can take lists or maps of
+    /// structured sources directly.
+    fn parse_value(val: Value) -> Result<Self, StdError>
+    where
+        Self: Sized,
+    {
+        Self::parse(&val.to_string())
+    }
+
     fn usage() -> Result<String, YasecError>
     where
         Self: Sized,
     {
-        Self::usage_prefix("")
+        Self::usage_prefix(Self::root_prefix())
     }
 
     fn usage_prefix(prefix: impl AsRef<str>) -> Result<String, YasecError>
     where
         Self: Sized,
     {
-        let header = format!("{: <24}\t{: <32}\t{}", "NAME", "TYPE", "DEFAULT");
-        let delimiter = format!("{}", "-".repeat(90));
-        let values = Self::usage_with_context(Context::new(prefix))?
-            .iter()
-            .map(format_field_usage)
-            .collect::<Vec<_>>()
-            .join("\n");
-        Ok(format!("{}\n{}\n{}", header, delimiter, values))
+        let header = format!(
+            "{: <24}\t{: <32}\t{: <16}\t{}",
+            "NAME", "TYPE", "DEFAULT", "DESCRIPTION"
+        );
+        let delimiter = "-".repeat(110);
+        let mut lines = vec![];
+        let mut condition = None;
+        for context in Self::usage_with_context(Context::new(prefix))?.iter() {
+            if context.get_condition() != condition {
+                condition = context.get_condition();
+                lines.push(String::new());
+                if let Some(ref condition) = condition {
+                    lines.push(format!("when {}:", condition));
+                }
+            }
+            lines.push(format_field_usage(context));
+        }
+        Ok(format!("{}\n{}\n{}", header, delimiter, lines.join("\n")))
     }
 
     fn usage_with_context(context: Context) -> Result<Vec<Context>, YasecError>
     where
         Self: Sized,
     {
+        check_var_name(&context)?;
         Ok(vec![context])
     }
 }
 
+/// Returns an error if the variable has no name, e.g. a field of a basic type is flattened.
+fn check_var_name(context: &Context) -> Result<(), YasecError> {
+    match context.infer_var_name().is_empty() {
+        true => Err(YasecError::FlattenedLeaf(context.get_var_type())),
+        false => Ok(()),
+    }
+}
+
+/// Returns `true` if any of the variables or, if it's allowed, a variable with `_FILE` suffix is
+/// set.
+fn is_var_set(context: &Context, var_names: &[String]) -> bool {
+    let source = context.source();
+    var_names.iter().any(|name| {
+        !matches!(source.lookup_value(name, context.path()), Ok(None))
+            || context.is_file_suffix()
+                && !matches!(source.lookup(&format!("{}_FILE", name)), Ok(None))
+    })
+}
+
+/// Reads and parses a file which path is set by a variable with `_FILE` suffix. The name and its
+/// aliases are tried in order. Returns `None` if the context doesn't allow it or no variable is set.
+fn parse_var_file<T: Yasec>(context: &Context) -> Option<Result<T, YasecError>> {
+    if !context.is_file_suffix() {
+        return None;
+    }
+    let mut found = None;
+    for var_name in context.var_names() {
+        let file_var_name = format!("{}_FILE", var_name);
+        match context.source().lookup(&file_var_name) {
+            Ok(Some(path)) => {
+                found = Some((var_name, file_var_name, path));
+                break;
+            }
+            Ok(None) => (),
+            Err(e) => return Some(Err(e)),
+        }
+    }
+    let (var_name, file_var_name, path) = found?;
+    context.warn_if_deprecated(&file_var_name);
+    let content = match fs::read_to_string(&path) {
+        Ok(content) => content,
+        Err(e) => {
+            return Some(Err(YasecError::ReadVarFileError {
+                var_name: file_var_name,
+                path,
+                source: e,
+            }))
+        }
+    };
+    let value = match content.strip_suffix('\n') {
+        Some(value) => value.strip_suffix('\r').unwrap_or(value),
+        None => &content,
+    };
+    Some(T::parse(value).map_err(|e| {
+        let e = YasecError::ParseFileVarError {
+            var_name,
+            path,
+            source: e,
+        };
+        redact(context, e, &[value])
+    }))
+}
+
+/// Returns `true` if references to other variables are expanded in the value of the variable.
+/// Values of secret variables are always taken as is.
+fn is_interpolated(context: &Context) -> bool {
+    context.is_interpolation() && !context.is_secret()
+}
+
+/// Redacts values in the error if the variable is secret.
+fn redact(context: &Context, error: YasecError, values: &[&str]) -> YasecError {
+    match context.is_secret() {
+        true => error.redact(values),
+        false => error,
+    }
+}
+
+/// Sets names of secret variables of the structure to the context, unless they are set by an outer
+/// structure, so other variables can't reference them by interpolation. Used by the derive macro.
+#[doc(hidden)]
+pub fn collect_secret_vars<T: Yasec>(context: Context) -> Result<Context, YasecError> {
+    if context.has_secret_vars() {
+        return Ok(context);
+    }
+    let var_names = T::usage_with_context(context.clone())?
+        .iter()
+        .filter(|context| context.is_secret())
+        .flat_map(|context| context.var_names())
+        .collect();
+    Ok(context.with_secret_vars(var_names))
+}
+
+/// Returns an error if a field of a flattened structure uses the same variable as another field.
+/// Takes the usage of every field with the flag whether it's flattened. Other fields may share
+/// variables, e.g. by `env`. Used by the derive macro.
+#[doc(hidden)]
+pub fn check_duplicate_vars(fields: &[(bool, Vec<Context>)]) -> Result<(), YasecError> {
+    let fields = fields
+        .iter()
+        .map(|(flatten, contexts)| {
+            let vars = contexts
+                .iter()
+                .map(|context| (context.infer_var_name(), context.get_condition()))
+                .collect::<HashSet<_>>();
+            (*flatten, vars)
+        })
+        .collect::<Vec<_>>();
+    for (index, (flatten, vars)) in fields.iter().enumerate() {
+        if !flatten {
+            continue;
+        }
+        for (other_index, (_, other_vars)) in fields.iter().enumerate() {
+            if other_index == index {
+                continue;
+            }
+            if let Some((var_name, _)) = vars.intersection(other_vars).min() {
+                return Err(YasecError::DuplicateVar(var_name.clone()));
+            }
+        }
+    }
+    Ok(())
+}
+
 pub fn format_field_usage(context: &Context) -> String {
     format!(
-        "{: <24}\t{: <32}\t{}",
-        context.infer_var_name(),
+        "{: <24}\t{: <32}\t{: <16}\t{}",
+        context.var_names().join(", "),
         format_usage_type(context),
         format_usage_default(context),
+        format_usage_description(context),
     )
+    .trim_end()
+    .to_owned()
 }
 
 // NOTE replace std::collections::HashMap to HashMap
@@ -112,8 +388,20 @@ pub fn format_usage_type(context: &Context) -> String {
         .to_string()
 }
 
+pub fn format_usage_description(context: &Context) -> String {
+    let constraints = context.get_constraints();
+    let constraints = (!constraints.is_empty()).then(|| format!("[{}]", constraints.join("; ")));
+    context
+        .get_description()
+        .into_iter()
+        .chain(constraints)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
 pub fn format_usage_default(context: &Context) -> String {
     match context.get_default_value() {
+        Some(_) if context.is_secret() => REDACTED.to_owned(),
         Some(v) => v,
         None => "-".to_string(),
     }
@@ -156,10 +444,17 @@ impl Yasec for String {
 
 impl Yasec for Vec<String> {
     fn parse(val: &str) -> Result<Self, StdError> {
-        return Ok(val
+        Ok(val
             .split(',')
             .map(|s| s.to_string())
-            .collect::<Vec<String>>());
+            .collect::<Vec<String>>())
+    }
+
+    fn parse_value(val: Value) -> Result<Self, StdError> {
+        match val {
+            Value::List(items) => Ok(items),
+            val => Self::parse(&val.to_string()),
+        }
     }
 }
 
@@ -172,6 +467,16 @@ impl Yasec for Vec<i32> {
             .map_err(|e| e.into());
         result
     }
+
+    fn parse_value(val: Value) -> Result<Self, StdError> {
+        match val {
+            Value::List(items) => Ok(items
+                .iter()
+                .map(|s| s.trim().parse::<i32>())
+                .collect::<Result<Vec<i32>, std::num::ParseIntError>>()?),
+            val => Self::parse(&val.to_string()),
+        }
+    }
 }
 
 impl Yasec for HashMap<String, String> {
@@ -185,20 +490,31 @@ impl Yasec for HashMap<String, String> {
             .collect::<Result<HashMap<_, _>, YasecError>>()?;
         Ok(v)
     }
+
+    fn parse_value(val: Value) -> Result<Self, StdError> {
+        match val {
+            Value::Map(pairs) => Ok(pairs.into_iter().collect()),
+            val => Self::parse(&val.to_string()),
+        }
+    }
 }
 
 impl<T: Yasec> Yasec for Option<T> {
+    /// The value is present if the variable or a variable of a nested field is set. Nested fields
+    /// are checked by names built from their path only, so a variable shared with another field
+    /// by `env` doesn't make the value present.
     fn with_context(context: Context) -> Result<Self, YasecError> {
-        let env_var_name = context.prefix();
-        let found = std::env::vars()
-            .filter(|(k, _v)| k.starts_with(&env_var_name))
-            .collect::<Vec<(String, String)>>();
-
-        if found.len() == 0 {
-            return Ok(None);
+        let usage = T::usage_with_context(context.clone())?;
+        let is_set = |field: &Context| match field.prefix() == context.prefix() {
+            true => is_var_set(field, &field.var_names()),
+            false => {
+                field.infer_var_name() == field.prefix() && is_var_set(field, &[field.prefix()])
+            }
+        };
+        match usage.iter().any(is_set) {
+            true => Ok(Some(T::with_context(context)?)),
+            false => Ok(None),
         }
-
-        Ok(Some(T::with_context(context)?))
     }
 
     fn usage_with_context(context: Context) -> Result<Vec<Context>, YasecError> {
@@ -208,4 +524,8 @@ impl<T: Yasec> Yasec for Option<T> {
     fn parse(val: &str) -> Result<Self, StdError> {
         Ok(Some(T::parse(val)?))
     }
+
+    fn parse_value(val: Value) -> Result<Self, StdError> {
+        Ok(Some(T::parse_value(val)?))
+    }
 }
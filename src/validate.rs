@@ -0,0 +1,156 @@
+use super::context::Context;
+use super::YasecError;
+use regex::Regex;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Display;
+use std::sync::Mutex;
+
+/// Size of a value checked by `len` and `non_empty` validators.
+pub trait Length {
+    fn length(&self) -> usize;
+}
+
+impl Length for String {
+    fn length(&self) -> usize {
+        self.chars().count()
+    }
+}
+
+impl<T> Length for Vec<T> {
+    fn length(&self) -> usize {
+        self.len()
+    }
+}
+
+impl<K, V> Length for HashMap<K, V> {
+    fn length(&self) -> usize {
+        self.len()
+    }
+}
+
+impl<K, V> Length for BTreeMap<K, V> {
+    fn length(&self) -> usize {
+        self.len()
+    }
+}
+
+/// Checks `min <= value <= max`, a missing bound isn't checked.
+pub fn check_range<T: PartialOrd + Display>(
+    context: &Context,
+    value: &T,
+    min: Option<T>,
+    max: Option<T>,
+) -> Result<(), YasecError> {
+    let below = min.as_ref().is_some_and(|min| value < min);
+    let above = max.as_ref().is_some_and(|max| value > max);
+    match below || above {
+        true => Err(error(
+            context,
+            "value",
+            &format_args!("`{}`", value),
+            format!("must be in range {}", format_bounds(&min, &max)),
+        )),
+        false => Ok(()),
+    }
+}
+
+/// Checks `min <= value.length() <= max`, a missing bound isn't checked.
+pub fn check_len<T: Length>(
+    context: &Context,
+    value: &T,
+    min: Option<usize>,
+    max: Option<usize>,
+) -> Result<(), YasecError> {
+    let len = value.length();
+    match min.is_some_and(|min| len < min) || max.is_some_and(|max| len > max) {
+        true => Err(error(
+            context,
+            "length",
+            &len,
+            format!("must be in range {}", format_bounds(&min, &max)),
+        )),
+        false => Ok(()),
+    }
+}
+
+pub fn check_non_empty<T: Length>(context: &Context, value: &T) -> Result<(), YasecError> {
+    match value.length() {
+        0 => Err(YasecError::Validation {
+            var_name: context.infer_var_name(),
+            reason: "value must not be empty".to_owned(),
+        }),
+        _ => Ok(()),
+    }
+}
+
+/// Checks the value against the pattern. Compiled patterns are cached, so a field is matched
+/// without compiling its pattern on every load.
+///
+/// # Panics
+///
+/// Panics if the pattern is invalid, the derive macro rejects such patterns at compile time.
+pub fn check_regex<T: Display>(
+    context: &Context,
+    value: &T,
+    pattern: &str,
+) -> Result<(), YasecError> {
+    lazy_static::lazy_static! {
+        static ref PATTERNS: Mutex<HashMap<String, Regex>> = Mutex::new(HashMap::new());
+    }
+    let re = PATTERNS
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .entry(pattern.to_owned())
+        .or_insert_with(|| {
+            Regex::new(pattern).unwrap_or_else(|e| panic!("invalid pattern `{}`, {}", pattern, e))
+        })
+        .clone();
+    match re.is_match(&value.to_string()) {
+        true => Ok(()),
+        false => Err(error(
+            context,
+            "value",
+            &format_args!("`{}`", value),
+            format!("must match `{}`", pattern),
+        )),
+    }
+}
+
+pub fn check_one_of<T: Display>(
+    context: &Context,
+    value: &T,
+    allowed: &[&str],
+) -> Result<(), YasecError> {
+    match allowed.contains(&value.to_string().as_str()) {
+        true => Ok(()),
+        false => Err(error(
+            context,
+            "value",
+            &format_args!("`{}`", value),
+            format!("must be one of: {}", allowed.join(", ")),
+        )),
+    }
+}
+
+/// Returns the text of the range for errors and usage, e.g. `1..=65535` or `>= 1`.
+pub fn format_bounds<T: Display>(min: &Option<T>, max: &Option<T>) -> String {
+    match (min, max) {
+        (Some(min), Some(max)) => format!("{}..={}", min, max),
+        (Some(min), None) => format!(">= {}", min),
+        (None, Some(max)) => format!("<= {}", max),
+        (None, None) => "any".to_owned(),
+    }
+}
+
+/// Builds the error, e.g. "value `0` must be in range 1..=65535". What is checked, like the value
+/// or its length, isn't included for a secret variable.
+fn error(context: &Context, subject: &str, checked: &dyn Display, reason: String) -> YasecError {
+    let reason = match context.is_secret() {
+        true => format!("{} {}", subject, reason),
+        false => format!("{} {} {}", subject, checked, reason),
+    };
+    YasecError::Validation {
+        var_name: context.infer_var_name(),
+        reason,
+    }
+}
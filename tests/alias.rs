@@ -0,0 +1,51 @@
+mod common;
+
+use common::vars;
+use yasec::{Yasec, YasecError};
+
+#[derive(Yasec, Debug, PartialEq)]
+pub struct Config {
+    #[yasec(env = "DATABASE_URL", alias = "DB_URL", alias = "PG_URL")]
+    pub database_url: String,
+    #[yasec(alias = "LEGACY_PORT")]
+    pub port: Option<u16>,
+}
+
+#[test]
+fn test_aliases_in_order() {
+    let source = vars(&[("PG_URL", "pg"), ("DB_URL", "db")]);
+    let config = Config::init_from(&source).unwrap();
+    assert_eq!(config.database_url, "db");
+    assert_eq!(config.port, None);
+
+    let source = vars(&[("DATABASE_URL", "database"), ("PG_URL", "pg")]);
+    let config = Config::init_from(&source).unwrap();
+    assert_eq!(config.database_url, "database");
+
+    let source = vars(&[("PG_URL", "pg"), ("LEGACY_PORT", "80")]);
+    let config = Config::init_from(&source).unwrap();
+    assert_eq!(config.database_url, "pg");
+    assert_eq!(config.port, Some(80));
+}
+
+#[test]
+fn test_errors_list_all_names() {
+    let err = Config::init_from(&vars(&[])).err().unwrap();
+    assert_eq!(
+        err,
+        YasecError::EmptyVar("DATABASE_URL or DB_URL or PG_URL".to_owned())
+    );
+
+    let source = vars(&[("DB_URL", "db"), ("LEGACY_PORT", "x")]);
+    let err = Config::init_from(&source).err().unwrap();
+    assert!(
+        matches!(err, YasecError::ParseEnvError { ref var_name, .. } if var_name == "LEGACY_PORT")
+    );
+}
+
+#[test]
+fn test_usage() {
+    let usage = Config::usage().unwrap();
+    assert!(usage.contains("DATABASE_URL, DB_URL, PG_URL"));
+    assert!(usage.contains("PORT, LEGACY_PORT"));
+}
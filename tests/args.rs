@@ -0,0 +1,111 @@
+mod common;
+
+use common::vars;
+use yasec::{flag_name, Args, Layered, Source, Yasec, YasecError};
+
+#[derive(Yasec)]
+pub struct DB {
+    pub host: String,
+    pub port: u16,
+}
+
+#[derive(Yasec)]
+pub struct Config {
+    pub db: DB,
+    #[yasec(default = "false")]
+    pub debug: bool,
+    #[yasec(env = "LOG_LEVEL", default = "info")]
+    pub level: String,
+}
+
+#[test]
+fn test_flag_name() {
+    assert_eq!(flag_name("DB_HOST"), "--db-host");
+}
+
+#[test]
+fn test_flags() {
+    let args =
+        Args::parse::<Config>(["--db-host", "127.0.0.1", "--db-port=5432", "--debug"]).unwrap();
+    assert_eq!(args.lookup("DB_HOST").unwrap().unwrap(), "127.0.0.1");
+    assert_eq!(args.lookup("DB_PORT").unwrap().unwrap(), "5432");
+    assert_eq!(args.lookup("DEBUG").unwrap().unwrap(), "true");
+    assert_eq!(args.lookup("LOG_LEVEL").unwrap(), None);
+}
+
+#[test]
+fn test_flags_override_source() {
+    let base = vars(&[
+        ("DB_HOST", "localhost"),
+        ("DB_PORT", "1"),
+        ("LOG_LEVEL", "warn"),
+    ]);
+    let args =
+        Args::parse::<Config>(["--db-port", "2", "--debug", "false", "--log-level=debug"]).unwrap();
+
+    let config = Config::init_from(&Layered::new().layer(base).layer(args)).unwrap();
+    assert_eq!(config.db.host, "localhost");
+    assert_eq!(config.db.port, 2);
+    assert!(!config.debug);
+    assert_eq!(config.level, "debug");
+}
+
+#[test]
+fn test_unknown_flag() {
+    let err = Args::parse::<Config>(["--db-hots", "x"]).err().unwrap();
+    assert_eq!(
+        err,
+        YasecError::ArgumentError {
+            arg: "--db-hots".to_owned(),
+            reason: "unknown flag".to_owned(),
+        }
+    );
+}
+
+#[test]
+fn test_unknown_flag_hides_value() {
+    let err = Args::parse::<Config>(["--db-pasword=hunter2"])
+        .err()
+        .unwrap();
+    assert_eq!(
+        err,
+        YasecError::ArgumentError {
+            arg: "--db-pasword".to_owned(),
+            reason: "unknown flag".to_owned(),
+        }
+    );
+    assert!(!err.to_string().contains("hunter2"), "{}", err);
+}
+
+#[test]
+fn test_alias_flag() {
+    #[derive(Yasec)]
+    pub struct Config {
+        #[yasec(alias = "OLD_NAME")]
+        pub name: String,
+    }
+
+    let args = Args::parse::<Config>(["--old-name", "app"]).unwrap();
+    let base = vars(&[("NAME", "base")]);
+    let config = Config::init_from(&Layered::new().layer(base).layer(args)).unwrap();
+    assert_eq!(config.name, "app");
+}
+
+#[test]
+fn test_missing_value() {
+    let err = Args::parse::<Config>(["--db-port"]).err().unwrap();
+    assert!(matches!(err, YasecError::ArgumentError { .. }), "{:?}", err);
+}
+
+#[test]
+fn test_help() {
+    let err = Args::parse::<Config>(["--db-port", "1", "--help"])
+        .err()
+        .unwrap();
+    match err {
+        YasecError::HelpRequested(help) => {
+            assert!(help.contains(&Config::usage().unwrap()), "{}", help);
+        }
+        e => panic!("unexpected error {:?}", e),
+    }
+}
@@ -0,0 +1,71 @@
+//! Helpers shared by integration tests. Every test file uses a part of them.
+#![allow(dead_code)]
+
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+use std::ops::Deref;
+use std::path::{Path, PathBuf};
+
+/// Builds an in-memory source from pairs of names and values.
+pub fn vars(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+    pairs
+        .iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect()
+}
+
+/// A file or a directory in the temporary directory which is removed on drop. The name gets the
+/// process id, so test binaries running in parallel don't clash.
+pub struct TempPath {
+    path: PathBuf,
+}
+
+impl TempPath {
+    /// Creates a file with the content, e.g. `TempPath::file("config.toml", "...")`.
+    pub fn file(name: &str, content: &str) -> Self {
+        let ret = Self::new(name);
+        fs::write(&ret.path, content).unwrap();
+        ret
+    }
+
+    /// Creates an empty directory.
+    pub fn dir(name: &str) -> Self {
+        let ret = Self::new(name);
+        fs::create_dir_all(&ret.path).unwrap();
+        ret
+    }
+
+    fn new(name: &str) -> Self {
+        let path = env::temp_dir().join(format!("yasec-{}-{}", std::process::id(), name));
+        remove(&path);
+        Self { path }
+    }
+}
+
+fn remove(path: &Path) {
+    let _ = match path.is_dir() {
+        true => fs::remove_dir_all(path),
+        false => fs::remove_file(path),
+    };
+}
+
+impl Drop for TempPath {
+    fn drop(&mut self) {
+        remove(&self.path);
+    }
+}
+
+impl Deref for TempPath {
+    type Target = Path;
+
+    fn deref(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl AsRef<Path> for TempPath {
+    fn as_ref(&self) -> &Path {
+        &self.path
+    }
+}
@@ -0,0 +1,91 @@
+mod common;
+
+use std::collections::HashMap;
+use std::env;
+
+use common::vars;
+use yasec::{Context, Report, Warning, Yasec};
+
+#[derive(Yasec, Debug, PartialEq)]
+pub struct LegacyConfig {
+    pub host: String,
+}
+
+#[derive(Yasec, Debug, PartialEq)]
+pub struct Config {
+    #[yasec(
+        env = "DATABASE_URL",
+        alias(name = "DB_URL", deprecated = "use DATABASE_URL instead"),
+        alias = "PG_URL"
+    )]
+    pub database_url: String,
+    #[yasec(deprecated = "it's ignored since 2.0", default = "0")]
+    pub workers: u8,
+    #[yasec(deprecated = "use DATABASE_URL instead")]
+    pub legacy: Option<LegacyConfig>,
+}
+
+fn init(source: &HashMap<String, String>) -> (Config, Vec<Warning>) {
+    let report = Report::new();
+    let config =
+        Config::with_context(Context::from_source("", source).with_report(&report)).unwrap();
+    (config, report.warnings())
+}
+
+#[test]
+fn test_no_warnings() {
+    let (config, warnings) = init(&vars(&[("DATABASE_URL", "db")]));
+    assert_eq!(config.database_url, "db");
+    assert!(warnings.is_empty());
+
+    let (_, warnings) = init(&vars(&[("PG_URL", "db")]));
+    assert!(warnings.is_empty());
+}
+
+#[test]
+fn test_deprecated_alias() {
+    let (config, warnings) = init(&vars(&[("DB_URL", "db")]));
+    assert_eq!(config.database_url, "db");
+    assert_eq!(
+        warnings,
+        vec![Warning {
+            var_name: "DB_URL".to_owned(),
+            message: "use DATABASE_URL instead".to_owned(),
+        }]
+    );
+    assert_eq!(
+        warnings[0].to_string(),
+        "Variable `DB_URL` is deprecated, use DATABASE_URL instead"
+    );
+}
+
+#[test]
+fn test_deprecated_field() {
+    let source = vars(&[
+        ("DATABASE_URL", "db"),
+        ("WORKERS", "4"),
+        ("LEGACY_HOST", "localhost"),
+    ]);
+    let (config, warnings) = init(&source);
+    assert_eq!(config.workers, 4);
+    let names = warnings
+        .iter()
+        .map(|w| w.var_name.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(names, vec!["WORKERS", "LEGACY_HOST"]);
+}
+
+#[test]
+fn test_init_with_report() {
+    #[derive(Yasec, Debug)]
+    pub struct Config {
+        #[yasec(env = "YASEC_DEPRECATED_TEST", deprecated = "remove it")]
+        pub value: String,
+    }
+
+    env::set_var("YASEC_DEPRECATED_TEST", "x");
+    let (config, report) = Config::init_with_report().unwrap();
+    env::remove_var("YASEC_DEPRECATED_TEST");
+    assert_eq!(config.value, "x");
+    assert_eq!(report.warnings().len(), 1);
+}
@@ -0,0 +1,83 @@
+mod common;
+
+use std::env;
+use std::fs;
+
+use common::TempPath;
+use yasec::{Directory, Env, Layered, Source, Yasec, YasecError};
+
+#[derive(Yasec)]
+pub struct Config {
+    pub db_host: String,
+    pub db_password: String,
+    pub db_port: Option<u16>,
+}
+
+#[test]
+fn test_reads_files() {
+    let path = TempPath::dir("dir-plain");
+    fs::write(path.join("DB_HOST"), "localhost\n").unwrap();
+    fs::write(path.join("DB_PASSWORD"), "s3cr3t").unwrap();
+    fs::create_dir(path.join("nested")).unwrap();
+
+    let source = Directory::from_path(&path).unwrap();
+    let config = Config::init_from(&source).unwrap();
+    assert_eq!(config.db_host, "localhost");
+    assert_eq!(config.db_password, "s3cr3t");
+    assert_eq!(config.db_port, None);
+
+    let mut keys = source.keys();
+    keys.sort();
+    assert_eq!(keys, vec!["DB_HOST", "DB_PASSWORD"]);
+    assert_eq!(source.lookup("nested").unwrap(), None);
+    assert_eq!(source.lookup("../DB_HOST").unwrap(), None);
+}
+
+#[cfg(unix)]
+#[test]
+fn test_kubernetes_layout() {
+    use std::os::unix::fs::symlink;
+
+    let path = TempPath::dir("dir-k8s");
+    let data = path.join("..2024_01_01_00_00_00.000000000");
+    fs::create_dir(&data).unwrap();
+    fs::write(data.join("DB_HOST"), "db.svc").unwrap();
+    fs::write(data.join("DB_PASSWORD"), "s3cr3t\n").unwrap();
+    symlink(&data, path.join("..data")).unwrap();
+    symlink("..data/DB_HOST", path.join("DB_HOST")).unwrap();
+    symlink("..data/DB_PASSWORD", path.join("DB_PASSWORD")).unwrap();
+
+    let source = Directory::from_path(&path).unwrap();
+    let mut keys = source.keys();
+    keys.sort();
+    assert_eq!(keys, vec!["DB_HOST", "DB_PASSWORD"]);
+    assert_eq!(source.lookup("..data").unwrap(), None);
+
+    let config = Config::init_from(&source).unwrap();
+    assert_eq!(config.db_host, "db.svc");
+    assert_eq!(config.db_password, "s3cr3t");
+}
+
+#[test]
+fn test_layered_over_env() {
+    let path = TempPath::dir("dir-layered");
+    fs::write(path.join("DIR_TEST_PASSWORD"), "from-file").unwrap();
+    env::set_var("DIR_TEST_PASSWORD", "from-env");
+
+    #[derive(Yasec)]
+    pub struct Config {
+        pub dir_test_password: String,
+    }
+
+    let source = Layered::new()
+        .layer(Env)
+        .layer(Directory::from_path(&path).unwrap());
+    let config = Config::init_from(&source).unwrap();
+    assert_eq!(config.dir_test_password, "from-file");
+}
+
+#[test]
+fn test_missing_directory() {
+    let err = Directory::from_path("/nonexistent/yasec").err().unwrap();
+    assert!(matches!(err, YasecError::ReadFileError { .. }), "{:?}", err);
+}
@@ -0,0 +1,102 @@
+mod common;
+
+use std::env;
+
+use common::TempPath;
+use yasec::{Dotenv, Source, Yasec, YasecError};
+
+fn lookup(source: &Dotenv, name: &str) -> Option<String> {
+    source.lookup(name).unwrap()
+}
+
+#[test]
+fn test_syntax() {
+    let path = TempPath::file(
+        "syntax.env",
+        r#"
+# comment
+PLAIN=value
+SPACED = spaced value   # trailing comment
+export EXPORTED=yes
+HASH=a#b
+EMPTY=
+SINGLE='literal \n $HOME # not a comment'
+DOUBLE="line\nbreak \"quoted\" \$HOME" # comment
+MULTI="first
+second"
+PLAIN=overridden
+"#,
+    );
+
+    let dotenv = Dotenv::from_path(&path).unwrap();
+    assert_eq!(lookup(&dotenv, "PLAIN").unwrap(), "overridden");
+    assert_eq!(lookup(&dotenv, "SPACED").unwrap(), "spaced value");
+    assert_eq!(lookup(&dotenv, "EXPORTED").unwrap(), "yes");
+    assert_eq!(lookup(&dotenv, "HASH").unwrap(), "a#b");
+    assert_eq!(lookup(&dotenv, "EMPTY").unwrap(), "");
+    assert_eq!(
+        lookup(&dotenv, "SINGLE").unwrap(),
+        r"literal \n $HOME # not a comment"
+    );
+    assert_eq!(
+        lookup(&dotenv, "DOUBLE").unwrap(),
+        "line\nbreak \"quoted\" $HOME"
+    );
+    assert_eq!(lookup(&dotenv, "MULTI").unwrap(), "first\nsecond");
+    assert_eq!(lookup(&dotenv, "MISSING"), None);
+}
+
+#[test]
+fn test_malformed_line() {
+    let path = TempPath::file("malformed.env", "A=1\n\nnot a pair\n");
+
+    let err = Dotenv::from_path(&path).err().unwrap();
+    assert_eq!(
+        err,
+        YasecError::DotenvError {
+            path: path.display().to_string(),
+            line: 3,
+            reason: "expected `KEY=VALUE`".to_owned(),
+        }
+    );
+}
+
+#[test]
+fn test_unterminated_quote() {
+    let path = TempPath::file("unterminated.env", "A=1\nB=\"open\nstill open\n");
+
+    let err = Dotenv::from_path(&path).err().unwrap();
+    match err {
+        YasecError::DotenvError { line, .. } => assert_eq!(line, 2),
+        e => panic!("unexpected error {:?}", e),
+    }
+}
+
+#[test]
+fn test_missing_file() {
+    let err = Dotenv::from_path("/nonexistent/yasec/.env").err().unwrap();
+    assert!(matches!(err, YasecError::ReadFileError { .. }), "{:?}", err);
+}
+
+#[derive(Yasec)]
+pub struct Config {
+    pub dotenv_test_host: String,
+    pub dotenv_test_port: u16,
+}
+
+#[test]
+fn test_init_with_dotenv() {
+    let path = TempPath::file(
+        "init.env",
+        "DOTENV_TEST_HOST=file-host\nDOTENV_TEST_PORT=1000\n",
+    );
+    env::set_var("DOTENV_TEST_HOST", "env-host");
+
+    let config = Config::init_with_dotenv(&path).unwrap();
+    assert_eq!(config.dotenv_test_host, "env-host");
+    assert_eq!(config.dotenv_test_port, 1000);
+
+    let config = Config::init_with_dotenv_overlay(&path).unwrap();
+    assert_eq!(config.dotenv_test_host, "file-host");
+    assert_eq!(config.dotenv_test_port, 1000);
+}
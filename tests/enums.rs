@@ -0,0 +1,107 @@
+use std::collections::HashMap;
+
+use yasec::{Yasec, YasecError};
+
+#[derive(Yasec, Debug, PartialEq)]
+pub enum Mode {
+    Client,
+    #[yasec(rename = "server", alias = "srv", alias = "daemon")]
+    Server,
+}
+
+#[derive(Yasec, Debug, PartialEq)]
+pub enum Size {
+    #[yasec(rename = "größe", alias = "tamaño")]
+    Large,
+}
+
+#[derive(Yasec, Debug, PartialEq)]
+#[yasec(case_insensitive)]
+pub enum Level {
+    Debug,
+    #[yasec(alias = "warning")]
+    Warn,
+}
+
+#[test]
+fn test_variant_names() {
+    assert_eq!(Mode::parse("Client").unwrap(), Mode::Client);
+    assert_eq!(Mode::parse("server").unwrap(), Mode::Server);
+    assert_eq!(Mode::parse("srv").unwrap(), Mode::Server);
+    assert_eq!(Mode::parse("daemon").unwrap(), Mode::Server);
+    assert!(Mode::parse("CLIENT").is_err());
+    assert!(Mode::parse("Server").is_err());
+}
+
+#[test]
+fn test_non_ascii_names() {
+    assert_eq!(Size::parse("größe").unwrap(), Size::Large);
+    assert_eq!(Size::parse("tamaño").unwrap(), Size::Large);
+    let err = Size::parse("x").err().unwrap();
+    assert_eq!(
+        err.to_string(),
+        "Unknown value `x`, expected one of: größe, tamaño"
+    );
+}
+
+#[test]
+fn test_case_insensitive() {
+    assert_eq!(Level::parse("DEBUG").unwrap(), Level::Debug);
+    assert_eq!(Level::parse("debug").unwrap(), Level::Debug);
+    assert_eq!(Level::parse("Warning").unwrap(), Level::Warn);
+}
+
+#[test]
+fn test_error_lists_allowed_values() {
+    let err = Mode::parse("x").err().unwrap();
+    assert_eq!(
+        err.to_string(),
+        "Unknown value `x`, expected one of: Client, server, srv, daemon"
+    );
+}
+
+#[test]
+fn test_field() {
+    #[derive(Yasec)]
+    pub struct Config {
+        pub mode: Mode,
+        #[yasec(default = "debug")]
+        pub level: Level,
+    }
+
+    let mut source = HashMap::new();
+    source.insert("MODE".to_owned(), "srv".to_owned());
+    let config = Config::init_from(&source).unwrap();
+    assert_eq!(config.mode, Mode::Server);
+    assert_eq!(config.level, Level::Debug);
+
+    source.insert("MODE".to_owned(), "unknown".to_owned());
+    let err = Config::init_from(&source).err().unwrap();
+    match err {
+        YasecError::ParseEnvError { var_name, .. } => assert_eq!(var_name, "MODE"),
+        e => panic!("unexpected error {:?}", e),
+    }
+}
+
+#[test]
+fn test_escaped_env_names() {
+    #[derive(Yasec)]
+    pub struct Config {
+        #[yasec(env = r"APP_MODE")]
+        pub mode: Mode,
+        #[yasec(env = "APP_\x4cEVEL")]
+        pub level: Level,
+    }
+
+    let mut source = HashMap::new();
+    source.insert("APP_MODE".to_owned(), "server".to_owned());
+    source.insert("APP_LEVEL".to_owned(), "warn".to_owned());
+    let config = Config::init_from(&source).unwrap();
+    assert_eq!(config.mode, Mode::Server);
+    assert_eq!(config.level, Level::Warn);
+
+    let usage = Config::usage().unwrap();
+    assert!(usage.contains("APP_MODE"), "{}", usage);
+    assert!(usage.contains("APP_LEVEL"), "{}", usage);
+    assert!(!usage.contains('"'), "{}", usage);
+}
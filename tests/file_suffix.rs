@@ -0,0 +1,153 @@
+mod common;
+
+use std::num::ParseIntError;
+
+use common::{vars, TempPath};
+use yasec::{Context, Yasec, YasecError};
+
+#[derive(Yasec)]
+pub struct DB {
+    pub host: String,
+    #[yasec(file)]
+    pub password: String,
+    pub port: u16,
+}
+
+#[derive(Yasec)]
+pub struct Config {
+    pub db: DB,
+}
+
+#[test]
+fn test_reads_file() {
+    let file = TempPath::file("password", "s3cr3t\n");
+    let path = file.display().to_string();
+    let source = vars(&[
+        ("DB_HOST", "localhost"),
+        ("DB_PASSWORD_FILE", &path),
+        ("DB_PORT", "5432"),
+    ]);
+
+    let config = Config::init_from(&source).unwrap();
+    assert_eq!(config.db.password, "s3cr3t");
+}
+
+#[test]
+fn test_variable_wins() {
+    let file = TempPath::file("password-ignored", "from-file");
+    let path = file.display().to_string();
+    let source = vars(&[
+        ("DB_HOST", "localhost"),
+        ("DB_PASSWORD", "from-env"),
+        ("DB_PASSWORD_FILE", &path),
+        ("DB_PORT", "5432"),
+    ]);
+
+    let config = Config::init_from(&source).unwrap();
+    assert_eq!(config.db.password, "from-env");
+}
+
+#[test]
+fn test_opt_in() {
+    let file = TempPath::file("port", "5432\r\n");
+    let path = file.display().to_string();
+    let source = vars(&[
+        ("DB_HOST", "localhost"),
+        ("DB_PASSWORD", "from-env"),
+        ("DB_PORT_FILE", &path),
+    ]);
+
+    let err = Config::init_from(&source).err().unwrap();
+    assert_eq!(err, YasecError::EmptyVar("DB_PORT".to_owned()));
+
+    let config = Config::init_from_with_file_suffix(&source).unwrap();
+    assert_eq!(config.db.port, 5432);
+}
+
+#[test]
+fn test_global_with_root_prefix() {
+    #[derive(Yasec)]
+    #[yasec(prefix = "APP")]
+    pub struct AppConfig {
+        pub db: DB,
+    }
+
+    let file = TempPath::file("app-port", "5432\n");
+    let path = file.display().to_string();
+    let source = vars(&[
+        ("APP_DB_HOST", "localhost"),
+        ("APP_DB_PASSWORD", "s3cr3t"),
+        ("APP_DB_PORT_FILE", &path),
+    ]);
+
+    let config = AppConfig::init_from_with_file_suffix(&source).unwrap();
+    assert_eq!(config.db.port, 5432);
+}
+
+#[test]
+fn test_optional_with_env() {
+    #[derive(Yasec)]
+    pub struct Config {
+        #[yasec(env = "DB_PASS", file)]
+        pub pass: Option<String>,
+    }
+
+    let file = TempPath::file("optional-pass", "s3cr3t\n");
+    let path = file.display().to_string();
+    let config = Config::init_from(&vars(&[("DB_PASS_FILE", &path)])).unwrap();
+    assert_eq!(config.pass.as_deref(), Some("s3cr3t"));
+
+    let config = Config::init_from(&vars(&[])).unwrap();
+    assert_eq!(config.pass, None);
+}
+
+#[test]
+fn test_alias() {
+    #[derive(Yasec)]
+    pub struct Config {
+        #[yasec(alias = "OLD_PASS", file)]
+        pub pass: String,
+    }
+
+    let file = TempPath::file("alias-pass", "s3cr3t\n");
+    let path = file.display().to_string();
+    let config = Config::init_from(&vars(&[("OLD_PASS_FILE", &path)])).unwrap();
+    assert_eq!(config.pass, "s3cr3t");
+}
+
+#[test]
+fn test_missing_file() {
+    let source = vars(&[
+        ("DB_HOST", "localhost"),
+        ("DB_PASSWORD_FILE", "/nonexistent/yasec/password"),
+        ("DB_PORT", "5432"),
+    ]);
+
+    let err = Config::init_from(&source).err().unwrap();
+    match err {
+        YasecError::ReadVarFileError { var_name, path, .. } => {
+            assert_eq!(var_name, "DB_PASSWORD_FILE");
+            assert_eq!(path, "/nonexistent/yasec/password");
+        }
+        e => panic!("unexpected error {:?}", e),
+    }
+}
+
+#[test]
+fn test_invalid_file_content() {
+    use std::error::Error as _;
+
+    let file = TempPath::file("invalid-port", "not-a-port\n");
+    let path = file.display().to_string();
+    let source = vars(&[
+        ("DB_HOST", "localhost"),
+        ("DB_PASSWORD", "from-env"),
+        ("DB_PORT_FILE", &path),
+    ]);
+
+    let context = Context::from_source("", &source).with_file_suffix();
+    let err = Config::with_context(context).err().unwrap();
+    assert!(err.source().unwrap().is::<ParseIntError>());
+    assert!(err.to_string().contains(&path), "{}", err);
+    assert!(!err.to_string().contains("not-a-port"), "{}", err);
+}
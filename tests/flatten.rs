@@ -0,0 +1,116 @@
+mod common;
+
+use common::vars;
+use yasec::{Yasec, YasecError};
+
+#[derive(Yasec, Debug, PartialEq)]
+pub struct CommonConfig {
+    #[yasec(default = "info")]
+    pub log_level: String,
+}
+
+#[derive(Yasec, Debug, PartialEq)]
+pub struct DbConfig {
+    pub host: String,
+}
+
+#[derive(Yasec, Debug, PartialEq)]
+pub struct Config {
+    #[yasec(flatten)]
+    pub common: CommonConfig,
+    pub db: DbConfig,
+}
+
+#[test]
+fn test_flatten() {
+    let source = vars(&[("LOG_LEVEL", "debug"), ("DB_HOST", "localhost")]);
+    let config = Config::init_from(&source).unwrap();
+    assert_eq!(config.common.log_level, "debug");
+    assert_eq!(config.db.host, "localhost");
+
+    let config = Config::with_prefix_from("APP", &vars(&[("APP_DB_HOST", "db")])).unwrap();
+    assert_eq!(config.common.log_level, "info");
+
+    let usage = Config::usage().unwrap();
+    assert!(usage.contains("LOG_LEVEL"));
+    assert!(!usage.contains("COMMON_"));
+}
+
+#[test]
+fn test_collision() {
+    #[derive(Yasec, Debug)]
+    #[allow(dead_code)]
+    pub struct OtherConfig {
+        pub log_level: u8,
+    }
+
+    #[derive(Yasec, Debug)]
+    #[allow(dead_code)]
+    pub struct Config {
+        #[yasec(flatten)]
+        pub common: CommonConfig,
+        #[yasec(flatten)]
+        pub other: OtherConfig,
+    }
+
+    let source = vars(&[("LOG_LEVEL", "1")]);
+    let err = Config::init_from(&source).err().unwrap();
+    assert_eq!(err, YasecError::DuplicateVar("LOG_LEVEL".to_owned()));
+}
+
+#[test]
+fn test_shared_env_next_to_flatten() {
+    #[derive(Yasec, Debug)]
+    pub struct ServerConfig {
+        #[yasec(env = "HOST")]
+        pub host: String,
+    }
+
+    #[derive(Yasec, Debug)]
+    pub struct Config {
+        #[yasec(flatten)]
+        pub common: CommonConfig,
+        pub primary: ServerConfig,
+        pub replica: ServerConfig,
+    }
+
+    let source = vars(&[("HOST", "localhost")]);
+    let config = Config::init_from(&source).unwrap();
+    assert_eq!(config.common.log_level, "info");
+    assert_eq!(config.primary.host, "localhost");
+    assert_eq!(config.replica.host, "localhost");
+}
+
+#[test]
+fn test_optional() {
+    #[derive(Yasec, Debug)]
+    pub struct Config {
+        #[yasec(flatten)]
+        pub db: Option<DbConfig>,
+        pub port: u16,
+    }
+
+    let config = Config::init_from(&vars(&[("PORT", "80")])).unwrap();
+    assert_eq!(config.db, None);
+    assert_eq!(config.port, 80);
+
+    let config = Config::init_from(&vars(&[("PORT", "80"), ("HOST", "db")])).unwrap();
+    assert_eq!(config.db.unwrap().host, "db");
+}
+
+#[test]
+fn test_leaf() {
+    #[derive(Yasec, Debug)]
+    #[allow(dead_code)]
+    pub struct Config {
+        #[yasec(flatten)]
+        pub port: u16,
+    }
+
+    let err = Config::init_from(&vars(&[("PORT", "80")])).err().unwrap();
+    assert_eq!(err, YasecError::FlattenedLeaf("u16".to_owned()));
+    assert_eq!(
+        Config::usage().err().unwrap(),
+        YasecError::FlattenedLeaf("u16".to_owned())
+    );
+}
@@ -0,0 +1,81 @@
+mod common;
+
+use std::fmt::Debug;
+
+use common::vars;
+use yasec::Yasec;
+
+#[derive(Yasec, Debug, PartialEq)]
+pub struct DbConfig {
+    pub host: String,
+}
+
+#[derive(Yasec, Debug, PartialEq)]
+pub struct RedisConfig {
+    pub url: String,
+}
+
+#[derive(Yasec, Debug, PartialEq)]
+pub struct Pooled<T> {
+    pub inner: T,
+    #[yasec(default = "10")]
+    pub size: u32,
+}
+
+#[derive(Yasec, Debug, PartialEq)]
+pub struct Config {
+    pub db: Pooled<DbConfig>,
+    pub redis: Pooled<RedisConfig>,
+}
+
+#[derive(Yasec, Debug, PartialEq)]
+pub struct Tagged<T, M>
+where
+    M: Debug + Default,
+{
+    pub value: Option<T>,
+    #[yasec(default = "")]
+    pub label: String,
+    #[yasec(default = "0")]
+    pub count: u8,
+    pub marker: Wrapper<M>,
+}
+
+#[derive(Yasec, Debug, PartialEq)]
+pub struct Wrapper<M: Debug>(Pooled<M>);
+
+#[test]
+fn test_generic_struct() {
+    let source = vars(&[
+        ("DB_INNER_HOST", "localhost"),
+        ("REDIS_INNER_URL", "redis://localhost"),
+        ("REDIS_SIZE", "4"),
+    ]);
+    let config = Config::init_from(&source).unwrap();
+    assert_eq!(
+        config.db,
+        Pooled {
+            inner: DbConfig {
+                host: "localhost".to_owned(),
+            },
+            size: 10,
+        }
+    );
+    assert_eq!(config.redis.inner.url, "redis://localhost");
+    assert_eq!(config.redis.size, 4);
+}
+
+#[test]
+fn test_where_clause() {
+    let source = vars(&[("VALUE", "7"), ("MARKER_INNER", "3")]);
+    let tagged = Tagged::<u16, u8>::init_from(&source).unwrap();
+    assert_eq!(tagged.value, Some(7));
+    assert_eq!(tagged.marker, Wrapper(Pooled { inner: 3, size: 10 }));
+}
+
+#[test]
+fn test_usage() {
+    let usage = Pooled::<DbConfig>::usage_prefix("DB").unwrap();
+    assert!(usage.contains("DB_INNER_HOST"));
+    assert!(usage.contains("DB_SIZE"));
+}
@@ -0,0 +1,84 @@
+use yasec::Yasec;
+
+#[derive(Yasec)]
+#[allow(dead_code)]
+pub struct DbConfig {
+    /// Host of the database.
+    pub host: String,
+    #[yasec(default = "5432")]
+    pub port: u16,
+}
+
+#[derive(Yasec)]
+#[allow(dead_code)]
+pub struct Config {
+    /// URL which receives notifications
+    /// about finished jobs.
+    pub callback_url: String,
+    /// Ignored doc comment.
+    #[yasec(help = "Number of workers", default = "4")]
+    pub workers: u8,
+    pub db: DbConfig,
+}
+
+#[test]
+fn test_description_column() {
+    let usage = Config::usage().unwrap();
+    let lines = usage.lines().collect::<Vec<_>>();
+    assert!(lines[0].ends_with("DESCRIPTION"));
+
+    let row = |name: &str| {
+        lines
+            .iter()
+            .find(|l| l.starts_with(name))
+            .unwrap()
+            .split('\t')
+            .map(str::trim)
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(
+        row("CALLBACK_URL"),
+        vec![
+            "CALLBACK_URL",
+            "String",
+            "-",
+            "URL which receives notifications about finished jobs."
+        ]
+    );
+    assert_eq!(
+        row("WORKERS"),
+        vec!["WORKERS", "u8", "4", "Number of workers"]
+    );
+    assert_eq!(
+        row("DB_HOST"),
+        vec!["DB_HOST", "String", "-", "Host of the database."]
+    );
+    assert_eq!(row("DB_PORT"), vec!["DB_PORT", "u16", "5432"]);
+}
+
+#[test]
+fn test_escaped_and_non_ascii_text() {
+    #[derive(Yasec)]
+    #[allow(dead_code)]
+    pub struct Config {
+        #[yasec(help = "Größe der Datei")]
+        pub size: u64,
+        #[yasec(help = "a \"quoted\" word")]
+        pub word: String,
+    }
+
+    let usage = Config::usage().unwrap();
+    let description = |name: &str| {
+        usage
+            .lines()
+            .find(|l| l.starts_with(name))
+            .unwrap()
+            .split('\t')
+            .map(str::trim)
+            .next_back()
+            .unwrap()
+            .to_owned()
+    };
+    assert_eq!(description("SIZE"), "Größe der Datei");
+    assert_eq!(description("WORD"), "a \"quoted\" word");
+}
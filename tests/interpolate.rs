@@ -0,0 +1,192 @@
+mod common;
+
+use common::vars;
+use yasec::{Yasec, YasecError};
+
+#[derive(Yasec)]
+#[yasec(interpolate)]
+pub struct Config {
+    pub db_host: String,
+    #[yasec(default = "5432")]
+    pub db_port: u16,
+    #[yasec(default = "postgres://${DB_HOST}:${DB_PORT:-5432}/app")]
+    pub db_url: String,
+}
+
+#[test]
+fn test_default() {
+    let source = vars(&[("DB_HOST", "localhost")]);
+    let config = Config::init_from(&source).unwrap();
+    assert_eq!(config.db_url, "postgres://localhost:5432/app");
+
+    let source = vars(&[("DB_HOST", "localhost"), ("DB_PORT", "6432")]);
+    let config = Config::init_from(&source).unwrap();
+    assert_eq!(config.db_url, "postgres://localhost:6432/app");
+}
+
+#[test]
+fn test_value() {
+    #[derive(Yasec)]
+    pub struct Config {
+        #[yasec(interpolate)]
+        pub log_dir: String,
+    }
+
+    let source = vars(&[("HOME", "/home/app"), ("LOG_DIR", "${HOME}/logs")]);
+    let config = Config::init_from(&source).unwrap();
+    assert_eq!(config.log_dir, "/home/app/logs");
+}
+
+#[test]
+fn test_disabled_by_default() {
+    #[derive(Yasec)]
+    pub struct Config {
+        pub db_password: String,
+        pub log_dir: String,
+        #[yasec(default = "${HOME}/cache")]
+        pub cache_dir: String,
+    }
+
+    let source = vars(&[
+        ("HOME", "/home/app"),
+        ("DB_PASSWORD", "pa$$word${"),
+        ("LOG_DIR", "${HOME}/logs"),
+    ]);
+    let config = Config::init_from(&source).unwrap();
+    assert_eq!(config.db_password, "pa$$word${");
+    assert_eq!(config.log_dir, "${HOME}/logs");
+    assert_eq!(config.cache_dir, "${HOME}/cache");
+}
+
+#[test]
+fn test_secret_is_taken_as_is() {
+    #[derive(Yasec)]
+    #[yasec(interpolate)]
+    pub struct Config {
+        pub log_dir: String,
+        #[yasec(secret)]
+        pub db_password: String,
+    }
+
+    let source = vars(&[
+        ("HOME", "/home/app"),
+        ("LOG_DIR", "${HOME}/logs"),
+        ("DB_PASSWORD", "pa$$word${HOME"),
+    ]);
+    let config = Config::init_from(&source).unwrap();
+    assert_eq!(config.log_dir, "/home/app/logs");
+    assert_eq!(config.db_password, "pa$$word${HOME");
+}
+
+#[test]
+fn test_secret_reference() {
+    #[derive(Yasec)]
+    #[allow(dead_code)]
+    pub struct Credentials {
+        #[yasec(secret)]
+        pub api_secret: String,
+    }
+
+    #[derive(Yasec)]
+    #[yasec(interpolate)]
+    #[allow(dead_code)]
+    pub struct Config {
+        pub port: u16,
+        pub credentials: Credentials,
+    }
+
+    let source = vars(&[
+        ("PORT", "${CREDENTIALS_API_SECRET}"),
+        ("CREDENTIALS_API_SECRET", "topsecret"),
+    ]);
+    let err = Config::init_from(&source).err().unwrap();
+    assert!(!err.to_string().contains("topsecret"), "{}", err);
+    assert_eq!(
+        err,
+        YasecError::InterpolationError {
+            var_name: "PORT".to_owned(),
+            reason: "`CREDENTIALS_API_SECRET` is secret and can't be referenced".to_owned(),
+        }
+    );
+}
+
+#[test]
+fn test_nested_references() {
+    let source = vars(&[
+        ("DB_HOST", "${HOST_NAME}.${DOMAIN:-local}"),
+        ("HOST_NAME", "db"),
+        ("DB_URL", "${DB_USER-${DEFAULT_USER:-root}}@${DB_HOST}"),
+    ]);
+    let config = Config::init_from(&source).unwrap();
+    assert_eq!(config.db_host, "db.local");
+    assert_eq!(config.db_url, "root@db.local");
+}
+
+#[test]
+fn test_operators() {
+    #[derive(Yasec)]
+    #[yasec(interpolate)]
+    pub struct Config {
+        pub value: String,
+    }
+
+    let cases = [
+        ("${EMPTY:-x}", "x"),
+        ("${EMPTY-x}", ""),
+        ("${UNSET-x}", "x"),
+        ("${UNSET}", ""),
+        ("${SET:?required}", "1"),
+        ("$$HOME $${SET} $5", "$HOME ${SET} $5"),
+    ];
+    for (value, expected) in cases.iter() {
+        let source = vars(&[("EMPTY", ""), ("SET", "1"), ("VALUE", value)]);
+        let config = Config::init_from(&source).unwrap();
+        assert_eq!(&config.value, expected, "{}", value);
+    }
+}
+
+#[test]
+fn test_required() {
+    let source = vars(&[("DB_HOST", "${UNSET:?must be set}")]);
+    let err = Config::init_from(&source).err().unwrap();
+    assert_eq!(
+        err.errors()[0],
+        &YasecError::InterpolationError {
+            var_name: "DB_HOST".to_owned(),
+            reason: "`UNSET` must be set".to_owned(),
+        }
+    );
+}
+
+#[test]
+fn test_unterminated() {
+    let source = vars(&[("DB_HOST", "${UNSET")]);
+    let err = Config::init_from(&source).err().unwrap();
+    assert!(
+        matches!(err.errors()[0], YasecError::InterpolationError { .. }),
+        "{:?}",
+        err
+    );
+}
+
+#[test]
+fn test_cycle() {
+    let source = vars(&[("DB_HOST", "${A}"), ("A", "${B}"), ("B", "x${DB_HOST}")]);
+    let err = Config::init_from(&source).err().unwrap();
+    let err = err.errors()[0];
+    assert_eq!(
+        err,
+        &YasecError::InterpolationCycle {
+            chain: vec![
+                "DB_HOST".to_owned(),
+                "A".to_owned(),
+                "B".to_owned(),
+                "DB_HOST".to_owned(),
+            ],
+        }
+    );
+    assert_eq!(
+        err.to_string(),
+        "Interpolation of variable `DB_HOST` failed, cycle `DB_HOST -> A -> B -> DB_HOST`"
+    );
+}
@@ -0,0 +1,55 @@
+#![cfg(feature = "json")]
+
+mod common;
+
+use std::collections::HashMap;
+
+use common::TempPath;
+use yasec::{Document, Yasec, YasecError};
+
+#[derive(Yasec)]
+pub struct DB {
+    pub host: String,
+    pub port: u16,
+}
+
+#[derive(Yasec)]
+pub struct Config {
+    pub db: DB,
+    pub tags: Vec<String>,
+    pub labels: HashMap<String, String>,
+    pub replica: Option<DB>,
+}
+
+#[test]
+fn test_inits_from_json() {
+    let path = TempPath::file(
+        "init.json",
+        r#"{
+            "db": {"host": "localhost", "port": 5432},
+            "tags": ["a", "b"],
+            "labels": {"team": "core", "enabled": true},
+            "replica": null
+        }"#,
+    );
+
+    let config = Config::init_from(&Document::from_json_path(&path).unwrap()).unwrap();
+    assert_eq!(config.db.host, "localhost");
+    assert_eq!(config.db.port, 5432);
+    assert_eq!(config.tags, vec!["a", "b"]);
+    assert_eq!(config.labels.get("team").unwrap(), "core");
+    assert_eq!(config.labels.get("enabled").unwrap(), "true");
+    assert!(config.replica.is_none());
+}
+
+#[test]
+fn test_malformed_file() {
+    let path = TempPath::file("malformed.json", "{\"db\": ");
+
+    let err = Document::from_json_path(&path).err().unwrap();
+    assert!(
+        matches!(err, YasecError::ParseFileError { .. }),
+        "{:?}",
+        err
+    );
+}
@@ -0,0 +1,76 @@
+mod common;
+
+use std::env;
+
+use common::{vars, TempPath};
+use yasec::{Dotenv, Env, Layered, Source, Yasec};
+
+#[derive(Yasec)]
+pub struct Config {
+    pub layered_host: String,
+    pub layered_port: u16,
+    pub layered_user: String,
+}
+
+#[test]
+fn test_top_layer_wins() {
+    let defaults = vars(&[
+        ("LAYERED_HOST", "default-host"),
+        ("LAYERED_PORT", "1"),
+        ("LAYERED_USER", "default-user"),
+    ]);
+    let site = vars(&[("LAYERED_PORT", "2"), ("LAYERED_USER", "site-user")]);
+    let overrides = vars(&[("LAYERED_USER", "cli-user")]);
+
+    let source = Layered::new()
+        .layer(&defaults)
+        .layer(&site)
+        .layer(&overrides);
+    let config = Config::init_from(&source).unwrap();
+    assert_eq!(config.layered_host, "default-host");
+    assert_eq!(config.layered_port, 2);
+    assert_eq!(config.layered_user, "cli-user");
+}
+
+#[test]
+fn test_underlay() {
+    let defaults = vars(&[("LAYERED_PORT", "1")]);
+    let site = vars(&[("LAYERED_PORT", "2")]);
+
+    let source = Layered::new().layer(site).underlay(defaults);
+    assert_eq!(source.lookup("LAYERED_PORT").unwrap().unwrap(), "2");
+    assert_eq!(source.lookup("LAYERED_HOST").unwrap(), None);
+}
+
+#[test]
+fn test_keys_are_merged() {
+    let source = Layered::new()
+        .layer(vars(&[("A", "1"), ("B", "2")]))
+        .layer(vars(&[("B", "3"), ("C", "4")]));
+    assert_eq!(source.keys(), vec!["A", "B", "C"]);
+}
+
+#[test]
+fn test_dotenv_and_env() {
+    let path = TempPath::file(
+        "layered.env",
+        "LAYERED_ENV_HOST=file-host\nLAYERED_ENV_PORT=1000\nLAYERED_ENV_USER=file-user\n",
+    );
+    env::set_var("LAYERED_ENV_HOST", "env-host");
+
+    #[derive(Yasec)]
+    pub struct Config {
+        pub layered_env_host: String,
+        pub layered_env_port: u16,
+        pub layered_env_user: String,
+    }
+
+    let source = Layered::new()
+        .layer(Dotenv::from_path(&path).unwrap())
+        .layer(Env)
+        .layer(vars(&[("LAYERED_ENV_USER", "cli-user")]));
+    let config = Config::init_from(&source).unwrap();
+    assert_eq!(config.layered_env_host, "env-host");
+    assert_eq!(config.layered_env_port, 1000);
+    assert_eq!(config.layered_env_user, "cli-user");
+}
@@ -62,3 +62,52 @@ fn test_duplicated_are_allowed() {
     assert_eq!(config.db2.host, "localhost");
     assert_eq!(config.db2.port, 5432u16);
 }
+
+#[test]
+fn test_collects_all_errors() {
+    use std::collections::HashMap;
+
+    let mut source = HashMap::new();
+    source.insert("DB1_HOST".to_owned(), "localhost".to_owned());
+    source.insert("DB1_PORT".to_owned(), "x".to_owned());
+
+    #[allow(dead_code)]
+    #[derive(Yasec)]
+    pub struct DB {
+        pub host: String,
+        pub port: u16,
+    }
+
+    #[allow(dead_code)]
+    #[derive(Yasec)]
+    pub struct Config {
+        pub db1: DB,
+        pub db2: DB,
+        pub listen_port: u16,
+    }
+
+    let err = Config::init_from(&source).err().unwrap();
+    let errors = err.errors();
+    assert_eq!(errors.len(), 4, "{}", err);
+    assert!(
+        matches!(errors[0], yasec::YasecError::ParseEnvError { var_name, .. } if var_name == "DB1_PORT")
+    );
+    assert_eq!(
+        errors[1],
+        &yasec::YasecError::EmptyVar("DB2_HOST".to_owned())
+    );
+    assert_eq!(
+        errors[2],
+        &yasec::YasecError::EmptyVar("DB2_PORT".to_owned())
+    );
+    assert_eq!(
+        errors[3],
+        &yasec::YasecError::EmptyVar("LISTEN_PORT".to_owned())
+    );
+    assert!(
+        err.to_string()
+            .starts_with("Configuration failed with 4 errors:\n  - "),
+        "{}",
+        err
+    );
+}
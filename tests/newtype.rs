@@ -0,0 +1,75 @@
+mod common;
+
+use common::vars;
+use yasec::{Yasec, YasecError};
+
+#[derive(Yasec, Debug, PartialEq)]
+pub struct Port(u16);
+
+#[derive(Yasec, Debug, PartialEq)]
+pub struct ApiKey(String);
+
+#[derive(Yasec, Debug, PartialEq)]
+pub struct Hosts(Vec<String>);
+
+#[derive(Yasec, Debug, PartialEq)]
+pub struct DbConfig {
+    pub host: String,
+    #[yasec(default = "5432")]
+    pub port: Port,
+}
+
+#[derive(Yasec, Debug, PartialEq)]
+pub struct Primary(DbConfig);
+
+#[derive(Yasec, Debug, PartialEq)]
+pub struct Config {
+    pub api_key: ApiKey,
+    pub hosts: Hosts,
+    pub db: Primary,
+}
+
+#[test]
+fn test_newtypes() {
+    let source = vars(&[
+        ("API_KEY", "secret"),
+        ("HOSTS", "a,b"),
+        ("DB_HOST", "localhost"),
+    ]);
+    let config = Config::init_from(&source).unwrap();
+    assert_eq!(config.api_key, ApiKey("secret".to_owned()));
+    assert_eq!(config.hosts, Hosts(vec!["a".to_owned(), "b".to_owned()]));
+    assert_eq!(
+        config.db,
+        Primary(DbConfig {
+            host: "localhost".to_owned(),
+            port: Port(5432),
+        })
+    );
+}
+
+#[test]
+fn test_parse_error() {
+    assert_eq!(Port::parse("80").unwrap(), Port(80));
+    assert!(Port::parse("x").is_err());
+
+    let source = vars(&[
+        ("API_KEY", "secret"),
+        ("HOSTS", "a"),
+        ("DB_HOST", "localhost"),
+        ("DB_PORT", "x"),
+    ]);
+    let err = Config::init_from(&source).err().unwrap();
+    assert!(matches!(err, YasecError::ParseEnvError { ref var_name, .. } if var_name == "DB_PORT"));
+}
+
+#[test]
+fn test_usage() {
+    let usage = Config::usage().unwrap();
+    let names = usage
+        .lines()
+        .skip(2)
+        .map(|l| l.split('\t').next().unwrap().trim())
+        .collect::<Vec<_>>();
+    assert_eq!(names, vec!["API_KEY", "HOSTS", "DB_HOST", "DB_PORT"]);
+}
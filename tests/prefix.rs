@@ -0,0 +1,66 @@
+mod common;
+
+use common::vars;
+use yasec::{Args, Yasec};
+
+#[derive(Yasec, Debug, PartialEq)]
+pub struct DbConfig {
+    pub host: String,
+    #[yasec(default = "5432")]
+    pub port: u16,
+}
+
+#[derive(Yasec, Debug, PartialEq)]
+#[yasec(prefix = "MYAPP")]
+pub struct Config {
+    #[yasec(prefix = "PG")]
+    pub db: DbConfig,
+    pub name: String,
+}
+
+#[test]
+fn test_field_prefix() {
+    let source = vars(&[("MYAPP_PG_HOST", "localhost"), ("MYAPP_NAME", "app")]);
+    let config = Config::init_from(&source).unwrap();
+    assert_eq!(config.db.host, "localhost");
+    assert_eq!(config.db.port, 5432);
+    assert_eq!(config.name, "app");
+}
+
+#[test]
+fn test_container_prefix() {
+    assert_eq!(Config::root_prefix(), "MYAPP");
+
+    let source = vars(&[("OTHER_PG_HOST", "localhost"), ("OTHER_NAME", "app")]);
+    let config = Config::with_prefix_from("OTHER", &source).unwrap();
+    assert_eq!(config.name, "app");
+
+    let usage = Config::usage().unwrap();
+    assert!(usage.contains("MYAPP_PG_HOST"));
+    assert!(usage.contains("MYAPP_NAME"));
+    assert!(!usage.contains("DB_"));
+}
+
+#[test]
+fn test_nested_container_prefix_is_ignored() {
+    #[derive(Yasec, Debug)]
+    pub struct Outer {
+        pub inner: Config,
+    }
+
+    let source = vars(&[("INNER_PG_HOST", "localhost"), ("INNER_NAME", "app")]);
+    let outer = Outer::init_from(&source).unwrap();
+    assert_eq!(outer.inner.name, "app");
+}
+
+#[test]
+fn test_args() {
+    let args = Args::parse::<Config>(["--myapp-name", "app"]).unwrap();
+    let config = Config::init_from(
+        &yasec::Layered::new()
+            .layer(vars(&[("MYAPP_PG_HOST", "localhost")]))
+            .layer(args),
+    )
+    .unwrap();
+    assert_eq!(config.name, "app");
+}
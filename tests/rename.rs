@@ -0,0 +1,84 @@
+mod common;
+
+use common::vars;
+use yasec::{Context, Yasec};
+
+#[derive(Yasec, Debug, PartialEq)]
+#[allow(non_snake_case)]
+pub struct DbConfig {
+    pub dbHost: String,
+    #[yasec(default = "30")]
+    pub conn_timeout: u32,
+    #[yasec(default = "postgres")]
+    pub r#type: String,
+}
+
+#[derive(Yasec, Debug, PartialEq)]
+pub struct Config {
+    pub db: DbConfig,
+    #[yasec(rename = "HOSTNAME")]
+    pub host_name: String,
+}
+
+#[derive(Yasec, Debug, PartialEq)]
+#[yasec(rename_all = "kebab-case")]
+pub struct KebabConfig {
+    pub log_level: String,
+    #[yasec(rename = "Port")]
+    pub http_port: u16,
+}
+
+#[derive(Yasec, Debug, PartialEq)]
+#[yasec(rename_all = "snake_case")]
+pub enum Mode {
+    ReadOnly,
+    ReadWrite,
+}
+
+#[derive(Yasec, Debug, PartialEq)]
+#[yasec(separator = "__")]
+pub struct NestedConfig {
+    pub db: DbConfig,
+}
+
+#[test]
+fn test_default_names() {
+    let source = vars(&[
+        ("DB_DB_HOST", "localhost"),
+        ("DB_TYPE", "mysql"),
+        ("HOSTNAME", "app"),
+    ]);
+    let config = Config::init_from(&source).unwrap();
+    assert_eq!(config.db.dbHost, "localhost");
+    assert_eq!(config.db.r#type, "mysql");
+    assert_eq!(config.host_name, "app");
+}
+
+#[test]
+fn test_rename_all() {
+    let source = vars(&[("log-level", "debug"), ("Port", "80")]);
+    let config = KebabConfig::init_from(&source).unwrap();
+    assert_eq!(config.log_level, "debug");
+    assert_eq!(config.http_port, 80);
+
+    assert_eq!(Mode::parse("read_only").unwrap(), Mode::ReadOnly);
+    assert_eq!(Mode::parse("read_write").unwrap(), Mode::ReadWrite);
+    assert!(Mode::parse("ReadOnly").is_err());
+}
+
+#[test]
+fn test_separator() {
+    let source = vars(&[("DB__DB_HOST", "localhost"), ("DB__CONN_TIMEOUT", "5")]);
+    let config = NestedConfig::init_from(&source).unwrap();
+    assert_eq!(config.db.dbHost, "localhost");
+    assert_eq!(config.db.conn_timeout, 5);
+
+    let source = vars(&[("APP__DB__DB_HOST", "localhost"), ("APP__HOSTNAME", "app")]);
+    let context = Context::from_source("APP", &source).with_separator("__");
+    let config = Config::with_context(context).unwrap();
+    assert_eq!(config.db.dbHost, "localhost");
+    assert_eq!(config.db.conn_timeout, 30);
+
+    let usage = NestedConfig::usage().unwrap();
+    assert!(usage.contains("DB__CONN_TIMEOUT"));
+}
@@ -0,0 +1,74 @@
+mod common;
+
+use common::vars;
+use yasec::{Secret, Yasec, YasecError};
+
+#[derive(Yasec, Debug)]
+pub struct Config {
+    #[yasec(secret)]
+    pub api_secret: u64,
+    #[yasec(secret, default = "hunter2")]
+    pub password: String,
+    pub token: Secret<String>,
+}
+
+#[test]
+fn test_secret_values() {
+    let source = vars(&[("API_SECRET", "42"), ("TOKEN", "t0ken")]);
+    let config = Config::init_from(&source).unwrap();
+    assert_eq!(config.api_secret, 42);
+    assert_eq!(config.password, "hunter2");
+    assert_eq!(config.token.expose(), "t0ken");
+    assert_eq!(format!("{:?}", config.token), "Secret(***)");
+    assert!(!format!("{:?}", config).contains("t0ken"));
+}
+
+#[test]
+fn test_redacted_errors() {
+    let source = vars(&[("API_SECRET", "s3cr3t-value"), ("TOKEN", "t0ken")]);
+    let err = Config::init_from(&source).err().unwrap();
+    let message = err.to_string();
+    assert!(!message.contains("s3cr3t"), "{}", message);
+    assert!(!format!("{:?}", err).contains("s3cr3t"));
+    match err.errors()[0] {
+        YasecError::ParseEnvError {
+            var_name,
+            var_value,
+            ..
+        } => {
+            assert_eq!(var_name, "API_SECRET");
+            assert_eq!(var_value, "***");
+        }
+        e => panic!("unexpected error {:?}", e),
+    }
+}
+
+#[test]
+fn test_redacted_source_error() {
+    #[derive(Yasec, Debug, PartialEq)]
+    pub enum Mode {
+        Client,
+    }
+
+    #[derive(Yasec, Debug)]
+    #[allow(dead_code)]
+    pub struct Config {
+        #[yasec(secret)]
+        pub mode: Mode,
+    }
+
+    let err = Config::init_from(&vars(&[("MODE", "s3cr3t")]))
+        .err()
+        .unwrap();
+    assert_eq!(
+        err.to_string(),
+        "Configuration from environment variables failed. Variable: `MODE` with value `***`, Unknown value `***`, expected one of: Client"
+    );
+}
+
+#[test]
+fn test_usage_hides_defaults() {
+    let usage = Config::usage().unwrap();
+    assert!(!usage.contains("hunter2"));
+    assert!(usage.contains("***"));
+}
@@ -0,0 +1,66 @@
+mod common;
+
+use std::sync::Arc;
+
+use common::vars;
+use yasec::Yasec;
+
+#[derive(Debug, Default)]
+pub struct Metrics {
+    pub requests: u64,
+}
+
+fn hostname() -> String {
+    "test-host".to_owned()
+}
+
+mod defaults {
+    pub fn workers() -> usize {
+        4
+    }
+}
+
+#[derive(Yasec, Debug)]
+pub struct Config {
+    pub port: u16,
+    #[yasec(skip)]
+    pub metrics: Arc<Metrics>,
+    #[yasec(default_fn = "hostname")]
+    pub hostname: String,
+    #[yasec(default_fn = "defaults::workers")]
+    pub workers: usize,
+}
+
+#[test]
+fn test_computed_fields() {
+    let source = vars(&[("PORT", "80"), ("HOSTNAME", "ignored"), ("WORKERS", "1")]);
+    let config = Config::init_from(&source).unwrap();
+    assert_eq!(config.port, 80);
+    assert_eq!(config.metrics.requests, 0);
+    assert_eq!(config.hostname, "test-host");
+    assert_eq!(config.workers, 4);
+}
+
+#[test]
+fn test_usage_hides_computed_fields() {
+    let usage = Config::usage().unwrap();
+    let lines = usage.lines().skip(2).collect::<Vec<_>>();
+    assert_eq!(lines.len(), 1);
+    assert!(lines[0].starts_with("PORT"));
+}
+
+#[test]
+fn test_only_computed_fields() {
+    #[derive(Yasec, Debug)]
+    pub struct Runtime {
+        #[yasec(skip)]
+        pub metrics: Arc<Metrics>,
+        #[yasec(default_fn = "hostname")]
+        pub hostname: String,
+    }
+
+    let runtime = Runtime::init_from(&vars(&[])).unwrap();
+    assert_eq!(runtime.metrics.requests, 0);
+    assert_eq!(runtime.hostname, "test-host");
+    assert_eq!(Runtime::usage().unwrap().lines().skip(2).count(), 0);
+}
@@ -0,0 +1,62 @@
+mod common;
+
+use std::collections::BTreeMap;
+
+use common::vars;
+use yasec::Yasec;
+
+#[derive(Yasec)]
+pub struct DB {
+    pub host: String,
+    pub port: u16,
+}
+
+#[derive(Yasec)]
+pub struct Config {
+    pub db: DB,
+    pub label: Option<String>,
+    #[yasec(default = "8080")]
+    pub listen_port: u16,
+}
+
+#[test]
+fn test_inits_from_map() {
+    let source = vars(&[("DB_HOST", "localhost"), ("DB_PORT", "5432")]);
+
+    let config = Config::init_from(&source).unwrap();
+    assert_eq!(config.db.host, "localhost");
+    assert_eq!(config.db.port, 5432);
+    assert_eq!(config.label, None);
+    assert_eq!(config.listen_port, 8080);
+}
+
+#[test]
+fn test_optional_from_map() {
+    let source = vars(&[
+        ("DB_HOST", "localhost"),
+        ("DB_PORT", "5432"),
+        ("LABEL", "primary"),
+    ]);
+
+    let config = Config::init_from(&source).unwrap();
+    assert_eq!(config.label, Some("primary".to_owned()));
+}
+
+#[test]
+fn test_missing_in_map() {
+    let source = vars(&[("DB_HOST", "localhost")]);
+
+    let err = Config::init_from(&source).err().unwrap();
+    assert_eq!(err, yasec::YasecError::EmptyVar("DB_PORT".to_owned()));
+}
+
+#[test]
+fn test_prefix_from_btree_map() {
+    let source: BTreeMap<String, String> = vars(&[("APP_DB_HOST", "db"), ("APP_DB_PORT", "1")])
+        .into_iter()
+        .collect();
+
+    let config = Config::with_prefix_from("APP", &source).unwrap();
+    assert_eq!(config.db.host, "db");
+    assert_eq!(config.db.port, 1);
+}
@@ -0,0 +1,87 @@
+mod common;
+
+use common::vars;
+use yasec::{Yasec, YasecError};
+
+#[derive(Yasec, Debug)]
+#[yasec(validate = "PoolConfig::check")]
+pub struct PoolConfig {
+    #[yasec(default = "1")]
+    pub min_conns: u32,
+    #[yasec(default = "10")]
+    pub max_conns: u32,
+}
+
+impl PoolConfig {
+    fn check(&self) -> Result<(), String> {
+        match self.min_conns <= self.max_conns {
+            true => Ok(()),
+            false => Err(format!(
+                "min_conns {} is greater than max_conns {}",
+                self.min_conns, self.max_conns
+            )),
+        }
+    }
+}
+
+#[derive(Yasec, Debug)]
+#[yasec(validate = "tls::both_or_none")]
+pub struct TlsConfig {
+    pub cert: Option<String>,
+    pub key: Option<String>,
+}
+
+mod tls {
+    pub fn both_or_none(config: &super::TlsConfig) -> Result<(), &'static str> {
+        match (&config.cert, &config.key) {
+            (Some(_), None) | (None, Some(_)) => Err("cert and key must be set together"),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[derive(Yasec, Debug)]
+pub struct Config {
+    pub db_pool: PoolConfig,
+    pub tls: TlsConfig,
+}
+
+#[test]
+fn test_valid() {
+    let source = vars(&[("TLS_CERT", "cert.pem"), ("TLS_KEY", "key.pem")]);
+    let config = Config::init_from(&source).unwrap();
+    assert_eq!(config.db_pool.max_conns, 10);
+    assert!(config.tls.cert.is_some());
+}
+
+#[test]
+fn test_invalid() {
+    let source = vars(&[("DB_POOL_MIN_CONNS", "20"), ("TLS_CERT", "cert.pem")]);
+    let err = Config::init_from(&source).err().unwrap();
+    let messages = err
+        .errors()
+        .iter()
+        .map(|e| e.to_string())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        messages,
+        vec![
+            "Validation of variables with prefix `DB_POOL` failed, min_conns 20 is greater than max_conns 10",
+            "Validation of variables with prefix `TLS` failed, cert and key must be set together",
+        ]
+    );
+    assert!(matches!(
+        err.errors()[0],
+        YasecError::StructValidation { prefix, .. } if prefix == "DB_POOL"
+    ));
+}
+
+#[test]
+fn test_root() {
+    let source = vars(&[("MIN_CONNS", "20")]);
+    let err = PoolConfig::init_from(&source).err().unwrap();
+    assert_eq!(
+        err.to_string(),
+        "Validation of the configuration failed, min_conns 20 is greater than max_conns 10"
+    );
+}
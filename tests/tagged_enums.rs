@@ -0,0 +1,126 @@
+mod common;
+
+use common::vars;
+use yasec::{Yasec, YasecError};
+
+#[derive(Yasec, Debug, PartialEq)]
+pub struct S3Config {
+    pub bucket: String,
+    #[yasec(default = "us-east-1")]
+    pub region: String,
+}
+
+#[derive(Yasec, Debug, PartialEq)]
+pub struct LocalConfig {
+    pub path: String,
+}
+
+#[derive(Yasec, Debug, PartialEq)]
+#[yasec(case_insensitive)]
+pub enum Storage {
+    S3(S3Config),
+    Local(LocalConfig),
+    Memory,
+}
+
+#[derive(Yasec, Debug, PartialEq)]
+pub struct Config {
+    pub storage: Storage,
+}
+
+#[test]
+fn test_selects_variant() {
+    let source = vars(&[
+        ("STORAGE_KIND", "s3"),
+        ("STORAGE_S3_BUCKET", "data"),
+        ("STORAGE_LOCAL_PATH", "/var/data"),
+    ]);
+    let config = Config::init_from(&source).unwrap();
+    assert_eq!(
+        config.storage,
+        Storage::S3(S3Config {
+            bucket: "data".to_owned(),
+            region: "us-east-1".to_owned(),
+        })
+    );
+
+    let source = vars(&[
+        ("STORAGE_KIND", "local"),
+        ("STORAGE_LOCAL_PATH", "/var/data"),
+    ]);
+    let config = Config::init_from(&source).unwrap();
+    assert_eq!(
+        config.storage,
+        Storage::Local(LocalConfig {
+            path: "/var/data".to_owned(),
+        })
+    );
+
+    let source = vars(&[("STORAGE_KIND", "memory")]);
+    let config = Config::init_from(&source).unwrap();
+    assert_eq!(config.storage, Storage::Memory);
+}
+
+#[test]
+fn test_requires_only_selected_variant() {
+    let source = vars(&[("STORAGE_KIND", "local")]);
+    let err = Config::init_from(&source).err().unwrap();
+    assert_eq!(err, YasecError::EmptyVar("STORAGE_LOCAL_PATH".to_owned()));
+
+    let err = Config::init_from(&vars(&[])).err().unwrap();
+    assert_eq!(err, YasecError::EmptyVar("STORAGE_KIND".to_owned()));
+}
+
+#[test]
+fn test_unknown_kind() {
+    let source = vars(&[("STORAGE_KIND", "gcs")]);
+    let err = Config::init_from(&source).err().unwrap();
+    assert_eq!(
+        err.to_string(),
+        "Configuration from environment variables failed. Variable: `STORAGE_KIND` with value `gcs`, Unknown value `gcs`, expected one of: S3, Local, Memory"
+    );
+}
+
+#[test]
+fn test_tag_and_default() {
+    #[derive(Yasec, Debug, PartialEq)]
+    #[yasec(tag = "TYPE")]
+    pub enum Backend {
+        #[yasec(rename = "local")]
+        Local(LocalConfig),
+    }
+
+    #[derive(Yasec, Debug, PartialEq)]
+    pub struct Config {
+        #[yasec(default = "local")]
+        pub backend: Backend,
+    }
+
+    let source = vars(&[("BACKEND_LOCAL_PATH", "/tmp")]);
+    let config = Config::init_from(&source).unwrap();
+    assert_eq!(
+        config.backend,
+        Backend::Local(LocalConfig {
+            path: "/tmp".to_owned(),
+        })
+    );
+
+    let source = vars(&[("BACKEND_TYPE", "other")]);
+    assert!(Config::init_from(&source).is_err());
+}
+
+#[test]
+fn test_usage() {
+    let usage = Config::usage().unwrap();
+    let lines = usage.lines().skip(2).collect::<Vec<_>>();
+    assert!(lines[0].starts_with("STORAGE_KIND"));
+    assert!(lines[0].contains("S3|Local|Memory"));
+    assert_eq!(lines[1], "");
+    assert_eq!(lines[2], "when STORAGE_KIND=S3:");
+    assert!(lines[3].starts_with("STORAGE_S3_BUCKET"));
+    assert!(lines[4].starts_with("STORAGE_S3_REGION"));
+    assert_eq!(lines[5], "");
+    assert_eq!(lines[6], "when STORAGE_KIND=Local:");
+    assert!(lines[7].starts_with("STORAGE_LOCAL_PATH"));
+    assert_eq!(lines.len(), 8);
+}
@@ -0,0 +1,137 @@
+#![cfg(feature = "toml")]
+
+mod common;
+
+use std::collections::HashMap;
+use std::env;
+
+use common::TempPath;
+use yasec::{Document, Env, Layered, Source, Yasec, YasecError};
+
+#[derive(Yasec)]
+pub struct DB {
+    pub host: String,
+    #[yasec(env = "DB_PORT")]
+    pub port: u16,
+}
+
+#[derive(Yasec)]
+pub struct Config {
+    pub db: DB,
+    pub replica: Option<DB>,
+    pub tags: Vec<String>,
+    pub weights: Vec<i32>,
+    pub labels: HashMap<String, String>,
+    #[yasec(default = "false")]
+    pub debug: bool,
+}
+
+const CONFIG: &str = r#"
+tags = ["a", "b,c"]
+weights = [1, 2, 3]
+
+[db]
+host = "localhost"
+port = 5432
+
+[labels]
+team = "core"
+"#;
+
+#[test]
+fn test_inits_from_toml() {
+    let path = TempPath::file("init.toml", CONFIG);
+
+    let config = Config::init_from(&Document::from_toml_path(&path).unwrap()).unwrap();
+    assert_eq!(config.db.host, "localhost");
+    assert_eq!(config.db.port, 5432);
+    assert!(config.replica.is_none());
+    assert_eq!(config.tags, vec!["a", "b,c"]);
+    assert_eq!(config.weights, vec![1, 2, 3]);
+    assert_eq!(config.labels.get("team").unwrap(), "core");
+    assert!(!config.debug);
+}
+
+#[test]
+fn test_root_prefix() {
+    #[derive(Yasec)]
+    #[yasec(prefix = "MYAPP")]
+    pub struct AppConfig {
+        pub db: DB,
+    }
+
+    let path = TempPath::file("prefix.toml", CONFIG);
+
+    let config = AppConfig::init_from(&Document::from_toml_path(&path).unwrap()).unwrap();
+    assert_eq!(config.db.host, "localhost");
+    assert_eq!(config.db.port, 5432);
+}
+
+#[test]
+fn test_flattened_names() {
+    let path = TempPath::file("flat.toml", CONFIG);
+
+    let document = Document::from_toml_path(&path).unwrap();
+    assert_eq!(document.lookup("DB_HOST").unwrap().unwrap(), "localhost");
+    assert_eq!(document.lookup("DB_PORT").unwrap().unwrap(), "5432");
+    assert_eq!(document.lookup("TAGS").unwrap().unwrap(), "a,b,c");
+}
+
+#[test]
+fn test_env_over_toml() {
+    let path = TempPath::file(
+        "layered.toml",
+        "[toml_test]\nhost = \"file-host\"\nport = 1\n",
+    );
+    env::set_var("TOML_TEST_PORT", "2");
+
+    #[derive(Yasec)]
+    pub struct Config {
+        pub toml_test: Server,
+    }
+
+    #[derive(Yasec)]
+    pub struct Server {
+        pub host: String,
+        pub port: u16,
+    }
+
+    let source = Layered::new()
+        .layer(Document::from_toml_path(&path).unwrap())
+        .layer(Env);
+    let config = Config::init_from(&source).unwrap();
+    assert_eq!(config.toml_test.host, "file-host");
+    assert_eq!(config.toml_test.port, 2);
+}
+
+#[test]
+fn test_invalid_value() {
+    let path = TempPath::file("invalid.toml", "[db]\nhost = \"localhost\"\nport = \"x\"\n");
+
+    let err = DB::with_prefix_from("DB", &Document::from_toml_path(&path).unwrap())
+        .err()
+        .unwrap();
+    match err {
+        YasecError::ParseEnvError {
+            var_name,
+            var_value,
+            ..
+        } => {
+            assert_eq!(var_name, "DB_PORT");
+            assert_eq!(var_value, "x");
+        }
+        e => panic!("unexpected error {:?}", e),
+    }
+}
+
+#[test]
+fn test_malformed_file() {
+    let path = TempPath::file("malformed.toml", "[db\n");
+
+    let err = Document::from_toml_path(&path).err().unwrap();
+    assert!(
+        matches!(err, YasecError::ParseFileError { .. }),
+        "{:?}",
+        err
+    );
+}
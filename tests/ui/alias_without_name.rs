@@ -0,0 +1,9 @@
+use yasec::Yasec;
+
+#[derive(Yasec)]
+struct Config {
+    #[yasec(alias(deprecated = "use PORT instead"))]
+    port: u16,
+}
+
+fn main() {}
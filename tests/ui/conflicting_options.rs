@@ -0,0 +1,9 @@
+use yasec::Yasec;
+
+#[derive(Yasec)]
+struct Config {
+    #[yasec(skip, default = "8080")]
+    port: u16,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use yasec::Yasec;
+
+#[derive(Yasec)]
+struct Config {
+    #[yasec(env = "PORT")]
+    #[yasec(env = "HTTP_PORT")]
+    port: u16,
+}
+
+fn main() {}
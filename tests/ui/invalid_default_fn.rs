@@ -0,0 +1,9 @@
+use yasec::Yasec;
+
+#[derive(Yasec)]
+struct Config {
+    #[yasec(default_fn = "num_cpus::get()")]
+    workers: usize,
+}
+
+fn main() {}
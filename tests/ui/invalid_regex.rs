@@ -0,0 +1,9 @@
+use yasec::Yasec;
+
+#[derive(Yasec)]
+struct Config {
+    #[yasec(regex = "[a-z")]
+    name: String,
+}
+
+fn main() {}
@@ -0,0 +1,6 @@
+use yasec::Yasec;
+
+#[derive(Yasec)]
+struct Port(#[yasec(default = "8080")] u16);
+
+fn main() {}
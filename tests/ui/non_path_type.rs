@@ -0,0 +1,8 @@
+use yasec::Yasec;
+
+#[derive(Yasec)]
+struct Config {
+    hosts: [String; 2],
+}
+
+fn main() {}
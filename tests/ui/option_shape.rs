@@ -0,0 +1,9 @@
+use yasec::Yasec;
+
+#[derive(Yasec)]
+struct Config {
+    #[yasec(file = "/run/secrets/db")]
+    db_password: String,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use yasec::Yasec;
+
+#[derive(Yasec)]
+enum Storage {
+    Local,
+    S3 { bucket: String },
+}
+
+fn main() {}
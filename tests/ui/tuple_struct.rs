@@ -0,0 +1,6 @@
+use yasec::Yasec;
+
+#[derive(Yasec)]
+struct Address(String, u16);
+
+fn main() {}
@@ -0,0 +1,9 @@
+use yasec::Yasec;
+
+#[derive(Yasec)]
+struct Config {
+    #[yasec(defualt = "8080")]
+    port: u16,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use yasec::Yasec;
+
+#[derive(Yasec)]
+#[yasec(tag = "KIND")]
+struct Config {
+    port: u16,
+}
+
+fn main() {}
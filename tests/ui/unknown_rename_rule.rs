@@ -0,0 +1,9 @@
+use yasec::Yasec;
+
+#[derive(Yasec)]
+#[yasec(rename_all = "snake")]
+struct Config {
+    port: u16,
+}
+
+fn main() {}
@@ -0,0 +1,102 @@
+mod common;
+
+use common::vars;
+use yasec::{Yasec, YasecError};
+
+#[derive(Yasec, Debug)]
+#[allow(dead_code)]
+pub struct Config {
+    #[yasec(non_empty)]
+    pub db_host: String,
+    #[yasec(range(min = 1, max = 65535), default = "5432")]
+    pub db_port: u32,
+    #[yasec(len(min = 2), default = "a,b")]
+    pub hosts: Vec<String>,
+    #[yasec(regex = "^[a-z]+$", default = "app")]
+    pub name: String,
+    #[yasec(one_of("debug", "info"), default = "info")]
+    pub log_level: String,
+    #[yasec(range(max = 10))]
+    pub workers: Option<u8>,
+    #[yasec(secret, len(min = 8), regex = "^[a-z0-9]+$", default = "password")]
+    pub password: String,
+}
+
+fn validation(var_name: &str, reason: &str) -> YasecError {
+    YasecError::Validation {
+        var_name: var_name.to_owned(),
+        reason: reason.to_owned(),
+    }
+}
+
+#[test]
+fn test_valid() {
+    let source = vars(&[("DB_HOST", "localhost"), ("WORKERS", "4")]);
+    let config = Config::init_from(&source).unwrap();
+    assert_eq!(config.db_port, 5432);
+    assert_eq!(config.workers, Some(4));
+}
+
+#[test]
+fn test_invalid() {
+    let source = vars(&[
+        ("DB_HOST", ""),
+        ("DB_PORT", "0"),
+        ("HOSTS", "a"),
+        ("NAME", "App"),
+        ("LOG_LEVEL", "trace"),
+        ("WORKERS", "11"),
+    ]);
+    let err = Config::init_from(&source).err().unwrap();
+    assert_eq!(
+        err.errors(),
+        vec![
+            &validation("DB_HOST", "value must not be empty"),
+            &validation("DB_PORT", "value `0` must be in range 1..=65535"),
+            &validation("HOSTS", "length 1 must be in range >= 2"),
+            &validation("NAME", "value `App` must match `^[a-z]+$`"),
+            &validation("LOG_LEVEL", "value `trace` must be one of: debug, info"),
+            &validation("WORKERS", "value `11` must be in range <= 10"),
+        ]
+    );
+}
+
+#[test]
+fn test_secret_is_not_echoed() {
+    let source = vars(&[("DB_HOST", "localhost"), ("PASSWORD", "S3CR3T-PASSWORD")]);
+    let err = Config::init_from(&source).err().unwrap();
+    assert_eq!(
+        err,
+        validation("PASSWORD", "value must match `^[a-z0-9]+$`")
+    );
+    assert!(!err.to_string().contains("S3CR3T"));
+}
+
+#[test]
+fn test_secret_length_is_not_echoed() {
+    let source = vars(&[("DB_HOST", "localhost"), ("PASSWORD", "abc")]);
+    let err = Config::init_from(&source).err().unwrap();
+    assert_eq!(err, validation("PASSWORD", "length must be in range >= 8"));
+}
+
+#[test]
+fn test_usage() {
+    let usage = Config::usage().unwrap();
+    let row = |name: &str| {
+        usage
+            .lines()
+            .skip(2)
+            .find(|l| l.starts_with(name))
+            .unwrap()
+            .split('\t')
+            .next_back()
+            .unwrap()
+            .to_owned()
+    };
+    assert_eq!(row("DB_HOST"), "[non-empty]");
+    assert_eq!(row("DB_PORT"), "[1..=65535]");
+    assert_eq!(row("HOSTS"), "[length >= 2]");
+    assert_eq!(row("NAME"), "[matches ^[a-z]+$]");
+    assert_eq!(row("LOG_LEVEL"), "[one of: debug, info]");
+    assert_eq!(row("PASSWORD"), "[length >= 8; matches ^[a-z0-9]+$]");
+}
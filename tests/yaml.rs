@@ -0,0 +1,66 @@
+#![cfg(feature = "yaml")]
+
+mod common;
+
+use std::collections::HashMap;
+
+use common::TempPath;
+use yasec::{Document, Source, Yasec, YasecError};
+
+#[derive(Yasec)]
+pub struct DB {
+    pub host: String,
+    pub port: u16,
+}
+
+#[derive(Yasec)]
+pub struct Config {
+    pub db: DB,
+    pub tags: Vec<String>,
+    pub weights: Vec<i32>,
+    pub labels: HashMap<String, String>,
+    pub note: Option<String>,
+}
+
+#[test]
+fn test_inits_from_yaml() {
+    let path = TempPath::file(
+        "init.yaml",
+        r#"
+db:
+  host: localhost
+  port: 5432
+tags: [a, "b,c"]
+weights:
+  - 1
+  - 2
+labels:
+  team: core
+  tier: 1
+note: ~
+"#,
+    );
+
+    let document = Document::from_yaml_path(&path).unwrap();
+    let config = Config::init_from(&document).unwrap();
+    assert_eq!(config.db.host, "localhost");
+    assert_eq!(config.db.port, 5432);
+    assert_eq!(config.tags, vec!["a", "b,c"]);
+    assert_eq!(config.weights, vec![1, 2]);
+    assert_eq!(config.labels.get("team").unwrap(), "core");
+    assert_eq!(config.labels.get("tier").unwrap(), "1");
+    assert_eq!(config.note, None);
+    assert_eq!(document.lookup("DB_HOST").unwrap().unwrap(), "localhost");
+}
+
+#[test]
+fn test_top_level_must_be_mapping() {
+    let path = TempPath::file("sequence.yaml", "- a\n- b\n");
+
+    let err = Document::from_yaml_path(&path).err().unwrap();
+    assert!(
+        matches!(err, YasecError::ParseFileError { .. }),
+        "{:?}",
+        err
+    );
+}
@@ -0,0 +1,109 @@
+//! Naming strategies of `rename_all` attribute.
+
+/// A case of variable names or enum values.
+#[derive(Clone, Copy, PartialEq)]
+pub enum RenameRule {
+    Lower,
+    Upper,
+    Pascal,
+    Camel,
+    Snake,
+    ScreamingSnake,
+    Kebab,
+    ScreamingKebab,
+}
+
+const RULES: &[(&str, RenameRule)] = &[
+    ("lowercase", RenameRule::Lower),
+    ("UPPERCASE", RenameRule::Upper),
+    ("PascalCase", RenameRule::Pascal),
+    ("camelCase", RenameRule::Camel),
+    ("snake_case", RenameRule::Snake),
+    ("SCREAMING_SNAKE_CASE", RenameRule::ScreamingSnake),
+    ("kebab-case", RenameRule::Kebab),
+    ("SCREAMING-KEBAB-CASE", RenameRule::ScreamingKebab),
+];
+
+impl RenameRule {
+    /// Returns the rule by its name or an error message listing known names.
+    pub fn from_name(name: &str) -> Result<Self, String> {
+        match RULES.iter().find(|(rule_name, _)| *rule_name == name) {
+            Some((_, rule)) => Ok(*rule),
+            None => Err(format!(
+                "unknown `rename_all` value `{}`, expected one of: {}",
+                name,
+                RULES
+                    .iter()
+                    .map(|(rule_name, _)| *rule_name)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )),
+        }
+    }
+
+    /// Converts an identifier like `db_host`, `dbHost` or `DbHost` to the case.
+    pub fn apply(self, ident: &str) -> String {
+        let words = split_words(ident);
+        let capitalized = || words.iter().map(|w| capitalize(w)).collect::<Vec<_>>();
+        match self {
+            RenameRule::Lower => words.concat().to_lowercase(),
+            RenameRule::Upper => words.concat().to_uppercase(),
+            RenameRule::Pascal => capitalized().concat(),
+            RenameRule::Camel => {
+                let mut ret = capitalized().concat();
+                if let Some(first) = words.first() {
+                    ret.replace_range(..first.len(), &first.to_lowercase());
+                }
+                ret
+            }
+            RenameRule::Snake => words.join("_").to_lowercase(),
+            RenameRule::ScreamingSnake => words.join("_").to_uppercase(),
+            RenameRule::Kebab => words.join("-").to_lowercase(),
+            RenameRule::ScreamingKebab => words.join("-").to_uppercase(),
+        }
+    }
+}
+
+/// Splits an identifier into words by underscores and case changes. An acronym is kept as one
+/// word, e.g. `HTTPServer` is `HTTP` and `Server`. Digits stick to the preceding word.
+fn split_words(ident: &str) -> Vec<&str> {
+    let chars = ident.char_indices().collect::<Vec<_>>();
+    let mut words = vec![];
+    let mut start = 0;
+    for (i, &(pos, c)) in chars.iter().enumerate() {
+        if c == '_' {
+            if start < pos {
+                words.push(&ident[start..pos]);
+            }
+            start = pos + 1;
+            continue;
+        }
+        let prev = if i > 0 { Some(chars[i - 1].1) } else { None };
+        let next = chars.get(i + 1).map(|&(_, c)| c);
+        let boundary = c.is_uppercase()
+            && match prev {
+                Some(p) if p.is_lowercase() || p.is_ascii_digit() => true,
+                Some(p) if p.is_uppercase() => next.is_some_and(|n| n.is_lowercase()),
+                _ => false,
+            };
+        if boundary && start < pos {
+            words.push(&ident[start..pos]);
+            start = pos;
+        }
+    }
+    if start < ident.len() {
+        words.push(&ident[start..]);
+    }
+    words
+}
+
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first
+            .to_uppercase()
+            .chain(chars.flat_map(char::to_lowercase))
+            .collect(),
+        None => String::new(),
+    }
+}
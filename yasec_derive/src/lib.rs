@@ -1,70 +1,569 @@
 //! Provides a derive macro that implements `Yasec` trait.
 //! For complete documentation please see [yasec](https://docs.rs/yasec).
 
+mod case;
+
+use case::RenameRule;
 use proc_macro::TokenStream;
 use quote::*;
+use syn::ext::IdentExt;
 use syn::punctuated::Punctuated;
 use syn::token::Comma;
 use syn::{Attribute, DeriveInput, Field, Fields, Ident, Lit, Meta, NestedMeta};
 
 #[proc_macro_derive(Yasec, attributes(yasec))]
 pub fn derive(input: TokenStream) -> TokenStream {
-    let derive_input: DeriveInput = syn::parse(input).unwrap();
-    let gen = impl_yasec(&derive_input);
-    gen.into()
+    let derive_input = syn::parse_macro_input!(input as DeriveInput);
+    impl_yasec(&derive_input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
 }
 
-fn impl_yasec(input: &DeriveInput) -> proc_macro2::TokenStream {
+fn impl_yasec(input: &DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
     use syn::Data::*;
-    let struct_name = &input.ident;
-
-    let inner_impl = match input.data {
+    match input.data {
         Struct(ref ds) => match ds.fields {
-            Fields::Named(ref fields) => impl_yasec_for_struct(struct_name, &fields.named),
-            _ => panic!("yasec supports only named fields"),
+            Fields::Named(ref fields) => impl_yasec_for_struct(input, &fields.named),
+            Fields::Unnamed(ref fields) if fields.unnamed.len() == 1 => {
+                impl_yasec_for_newtype(input, &fields.unnamed[0])
+            }
+            _ => Err(syn::Error::new_spanned(
+                &input.ident,
+                "yasec supports only structs with named fields and newtypes with one unnamed field",
+            )),
         },
-        _ => panic!("yasec only supports non-tuple structs"),
-    };
-
-    quote!(#inner_impl)
+        Enum(ref de) => impl_yasec_for_enum(input, de),
+        Union(_) => Err(syn::Error::new_spanned(
+            &input.ident,
+            "yasec supports only structs and enums",
+        )),
+    }
 }
 
 fn impl_yasec_for_struct(
-    struct_name: &Ident,
+    input: &DeriveInput,
     fields: &Punctuated<Field, Comma>,
-) -> proc_macro2::TokenStream {
-    let field_assigns = fields.iter().map(gen_field_assign);
-    let usage_assigns = fields.iter().map(gen_field_usage);
+) -> syn::Result<proc_macro2::TokenStream> {
+    let lists = fields
+        .iter()
+        .map(fetch_list_from_field)
+        .collect::<syn::Result<Vec<_>>>()?;
+    let field_attrs = fields
+        .iter()
+        .zip(&lists)
+        .map(|(field, list)| fetch_field_attrs(field, list))
+        .collect::<syn::Result<Vec<_>>>()?;
+    let impl_header = gen_impl_header(
+        input,
+        fields
+            .iter()
+            .zip(&field_attrs)
+            .filter(|(_, attrs)| attrs.is_loaded())
+            .map(|(field, _)| &field.ty),
+    );
+    let list = fetch_list_from_attrs(&input.attrs, STRUCT_OPTIONS)?;
+    let validate = find_item_in_list(&list, "validate")
+        .map(parse_path)
+        .transpose()?
+        .map(|validate| {
+            quote! {
+                if let Err(e) = #validate(&value) {
+                    return Err(::yasec::YasecError::StructValidation {
+                        prefix: context.prefix(),
+                        source: e.into(),
+                    });
+                }
+            }
+        });
+    let root_prefix = find_item_in_list(&list, "prefix").map(|prefix| {
+        quote! {
+            fn root_prefix() -> &'static str {
+                #prefix
+            }
+        }
+    });
+    let rename_all = fetch_rename_all(&list)?.unwrap_or(RenameRule::ScreamingSnake);
+    let separator = find_item_in_list(&list, "separator")
+        .map(|separator| quote!(let context = context.with_separator(#separator);));
+    let interpolation = has_flag_in_list(&list, "interpolate")
+        .then(|| quote!(let context = context.with_interpolation();));
+    let field_assigns = fields
+        .iter()
+        .zip(&field_attrs)
+        .enumerate()
+        .map(|(index, (field, attrs))| gen_field_assign(index, field, attrs, rename_all))
+        .collect::<syn::Result<Vec<_>>>()?;
+    let field_idents = fields.iter().map(|field| &field.ident);
+    let field_vars = (0..fields.len()).map(field_var).collect::<Vec<_>>();
+    let usage_assigns = fields
+        .iter()
+        .zip(&field_attrs)
+        .filter(|(_, attrs)| attrs.is_loaded())
+        .map(|(field, attrs)| gen_field_usage(field, attrs, rename_all))
+        .collect::<syn::Result<Vec<_>>>()?;
+
+    let flattens = field_attrs
+        .iter()
+        .filter(|attrs| attrs.is_loaded())
+        .map(|attrs| attrs.flatten);
+    let check_duplicates = field_attrs.iter().any(|attrs| attrs.flatten).then(|| {
+        quote! {
+            ::yasec::check_duplicate_vars(&[#((#flattens, #usage_assigns)),*])?;
+        }
+    });
+
+    Ok(quote! {
+        #impl_header {
+            #root_prefix
 
-    quote! {
-        impl Yasec for #struct_name {
             fn with_context(context: ::yasec::Context) -> ::std::result::Result<Self, ::yasec::YasecError> {
-                let config = Self {
-                    #(#field_assigns,)*
-                };
-                Ok(config)
+                #separator
+                #interpolation
+                let context = ::yasec::collect_secret_vars::<Self>(context)?;
+                #check_duplicates
+                let mut errors = ::std::vec::Vec::new();
+                #(#field_assigns)*
+                match (#(#field_vars,)*) {
+                    (#(Some(#field_vars),)*) => {
+                        let value = Self {
+                            #(#field_idents: #field_vars,)*
+                        };
+                        #validate
+                        Ok(value)
+                    }
+                    _ => Err(::yasec::YasecError::from_errors(errors)),
+                }
             }
 
             fn usage_with_context(context: ::yasec::Context) -> ::std::result::Result<Vec< ::yasec::Context>, ::yasec::YasecError> {
-                let output = vec![
+                #separator
+                let output: ::std::vec::Vec<::std::vec::Vec< ::yasec::Context>> = vec![
                     #(#usage_assigns,)*
+                ];
+                Ok(output.into_iter().flatten().collect())
+            }
+        }
+    })
+}
+
+/// Implements `Yasec` for a newtype like `struct Port(u16)` by delegating to the inner type.
+fn impl_yasec_for_newtype(
+    input: &DeriveInput,
+    field: &Field,
+) -> syn::Result<proc_macro2::TokenStream> {
+    fetch_list_from_attrs(&input.attrs, &[])?;
+    fetch_list_from_attrs(&field.attrs, &[])?;
+    let inner = &field.ty;
+    let impl_header = gen_impl_header(input, std::iter::once(inner));
+    Ok(quote! {
+        #impl_header {
+            fn with_context(context: ::yasec::Context) -> ::std::result::Result<Self, ::yasec::YasecError> {
+                <#inner as Yasec>::with_context(context).map(Self)
+            }
+
+            fn parse(val: &str) -> ::std::result::Result<Self, ::std::boxed::Box<dyn ::std::error::Error + Send + Sync + 'static>> {
+                <#inner as Yasec>::parse(val).map(Self)
+            }
+
+            fn parse_value(val: ::yasec::Value) -> ::std::result::Result<Self, ::std::boxed::Box<dyn ::std::error::Error + Send + Sync + 'static>> {
+                <#inner as Yasec>::parse_value(val).map(Self)
+            }
+
+            fn usage_with_context(context: ::yasec::Context) -> ::std::result::Result<Vec< ::yasec::Context>, ::yasec::YasecError> {
+                <#inner as Yasec>::usage_with_context(context)
+            }
+        }
+    })
+}
+
+fn impl_yasec_for_enum(
+    input: &DeriveInput,
+    data: &syn::DataEnum,
+) -> syn::Result<proc_macro2::TokenStream> {
+    let list = fetch_list_from_attrs(&input.attrs, ENUM_OPTIONS)?;
+    let case_insensitive = has_flag_in_list(&list, "case_insensitive");
+    let rename_all = fetch_rename_all(&list)?;
+    let tagged = data
+        .variants
+        .iter()
+        .any(|variant| !matches!(variant.fields, Fields::Unit));
+    if tagged {
+        return impl_yasec_for_tagged_enum(input, data, &list);
+    }
+
+    let mut allowed = vec![];
+    let mut arms = vec![];
+    for variant in data.variants.iter() {
+        let ident = &variant.ident;
+        let names = variant_names(variant, rename_all)?;
+        let matches = gen_variant_match(&names, case_insensitive);
+        allowed.extend(names);
+        arms.push(quote! {
+            if #matches {
+                return Ok(Self::#ident);
+            }
+        });
+    }
+
+    let impl_header = gen_impl_header(input, std::iter::empty());
+    Ok(quote! {
+        #impl_header {
+            fn parse(val: &str) -> ::std::result::Result<Self, ::std::boxed::Box<dyn ::std::error::Error + Send + Sync + 'static>> {
+                #(#arms)*
+                Err(::std::boxed::Box::new(::yasec::YasecError::UnknownVariant {
+                    value: val.to_owned(),
+                    allowed: vec![#(#allowed.to_owned()),*],
+                }))
+            }
+        }
+    })
+}
+
+/// Implements `Yasec` for an enum with variants holding a configuration, e.g. `S3(S3Config)`.
+/// The variant is selected by the `KIND` variable (or the one set by `tag`) and only its
+/// variables are read, prefixed by the variant name.
+fn impl_yasec_for_tagged_enum(
+    input: &DeriveInput,
+    data: &syn::DataEnum,
+    list: &Punctuated<NestedMeta, Comma>,
+) -> syn::Result<proc_macro2::TokenStream> {
+    let case_insensitive = has_flag_in_list(list, "case_insensitive");
+    let rename_all = fetch_rename_all(list)?;
+    let tag = find_item_in_list(list, "tag")
+        .map(lit_value)
+        .unwrap_or_else(|| "KIND".to_owned());
+
+    let impl_header = gen_impl_header(
+        input,
+        data.variants
+            .iter()
+            .flat_map(|variant| variant.fields.iter().map(|field| &field.ty)),
+    );
+    let mut allowed = vec![];
+    let mut arms = vec![];
+    let mut usages = vec![];
+    for variant in data.variants.iter() {
+        let ident = &variant.ident;
+        let names = variant_names(variant, rename_all)?;
+        let matches = gen_variant_match(&names, case_insensitive);
+        let primary_name = &names[0];
+        allowed.extend(names.clone());
+
+        let inner = match &variant.fields {
+            Fields::Unit => None,
+            Fields::Unnamed(fields) if fields.unnamed.len() == 1 => Some(&fields.unnamed[0].ty),
+            fields => {
+                return Err(syn::Error::new_spanned(
+                    fields,
+                    "yasec supports only unit variants and variants with one unnamed field",
+                ))
+            }
+        };
+        let inner = match inner {
+            Some(ty) => ty,
+            None => {
+                arms.push(quote! {
+                    if #matches {
+                        return Ok(Self::#ident);
+                    }
+                });
+                continue;
+            }
+        };
+        let prefix = RenameRule::ScreamingSnake.apply(&ident.unraw().to_string());
+        let variant_context = quote! {
+            context.with_var_name("", "").push_prefix(#prefix.to_owned())
+        };
+        arms.push(quote! {
+            if #matches {
+                return Ok(Self::#ident(<#inner as Yasec>::with_context(#variant_context)?));
+            }
+        });
+        usages.push(quote! {
+            {
+                let condition = format!("{}={}", tag_context.infer_var_name(), #primary_name);
+                <#inner as Yasec>::usage_with_context(#variant_context)?
+                    .into_iter()
+                    .map(|c| c.with_condition(&condition))
+                    .collect::<Vec< ::yasec::Context>>()
+            }
+        });
+    }
+    let tag_type = allowed.join("|");
+    let tag_context = quote! {
+        match context.get_default_value() {
+            Some(default) => context.push_prefix(#tag.to_owned()).with_var_type(#tag_type).with_default_value(&default),
+            None => context.push_prefix(#tag.to_owned()).with_var_type(#tag_type),
+        }
+    };
+
+    Ok(quote! {
+        #impl_header {
+            fn with_context(context: ::yasec::Context) -> ::std::result::Result<Self, ::yasec::YasecError> {
+                let tag_context = #tag_context;
+                let val = <String as Yasec>::with_context(tag_context.clone())?;
+                let val = val.as_str();
+                #(#arms)*
+                Err(::yasec::YasecError::ParseEnvError {
+                    var_name: tag_context.infer_var_name(),
+                    var_value: val.to_owned(),
+                    source: ::std::boxed::Box::new(::yasec::YasecError::UnknownVariant {
+                        value: val.to_owned(),
+                        allowed: vec![#(#allowed.to_owned()),*],
+                    }),
+                })
+            }
+
+            fn usage_with_context(context: ::yasec::Context) -> ::std::result::Result<Vec< ::yasec::Context>, ::yasec::YasecError> {
+                let tag_context = #tag_context;
+                let output = vec![
+                    vec![tag_context.clone()],
+                    #(#usages,)*
                 ].into_iter().flatten().collect::<Vec< ::yasec::Context>>();
                 Ok(output)
             }
         }
+    })
+}
+
+/// Generates `impl Yasec for Name<T>` with generics and the where clause of the item. A type
+/// parameter used in the types gets `Yasec` bound.
+fn gen_impl_header<'a>(
+    input: &DeriveInput,
+    types: impl Iterator<Item = &'a syn::Type>,
+) -> proc_macro2::TokenStream {
+    let name = &input.ident;
+    let mut generics = input.generics.clone();
+    let types = types.map(|ty| quote!(#ty)).collect::<Vec<_>>();
+    let bounded = input
+        .generics
+        .type_params()
+        .map(|param| &param.ident)
+        .filter(|ident| types.iter().any(|ty| contains_ident(ty.clone(), ident)))
+        .collect::<Vec<_>>();
+    let where_clause = generics.make_where_clause();
+    for ident in bounded {
+        where_clause
+            .predicates
+            .push(syn::parse_quote!(#ident: ::yasec::Yasec));
+    }
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    quote!(impl #impl_generics Yasec for #name #ty_generics #where_clause)
+}
+
+fn contains_ident(tokens: proc_macro2::TokenStream, ident: &Ident) -> bool {
+    tokens.into_iter().any(|token| match token {
+        proc_macro2::TokenTree::Ident(ref i) => i == ident,
+        proc_macro2::TokenTree::Group(group) => contains_ident(group.stream(), ident),
+        _ => false,
+    })
+}
+
+/// Returns the name of the variant followed by its aliases.
+fn variant_names(
+    variant: &syn::Variant,
+    rename_all: Option<RenameRule>,
+) -> syn::Result<Vec<String>> {
+    let list = fetch_list_from_attrs(&variant.attrs, VARIANT_OPTIONS)?;
+    let ident = variant.ident.unraw().to_string();
+    let name = find_item_in_list(&list, "rename")
+        .map(lit_value)
+        .unwrap_or_else(|| match rename_all {
+            Some(rule) => rule.apply(&ident),
+            None => ident,
+        });
+    Ok(std::iter::once(name)
+        .chain(
+            find_items_in_list(&list, "alias")
+                .into_iter()
+                .map(lit_value),
+        )
+        .collect())
+}
+
+/// Generates a condition that checks whether `val` is one of the names.
+fn gen_variant_match(names: &[String], case_insensitive: bool) -> proc_macro2::TokenStream {
+    let conditions = names.iter().map(|name| match case_insensitive {
+        true => quote!(val.eq_ignore_ascii_case(#name)),
+        false => quote!(val == #name),
+    });
+    quote!(#(#conditions)||*)
+}
+
+/// Options of a field set by `#[yasec(...)]` attribute.
+struct FieldAttrs<'a> {
+    env: Option<&'a Lit>,
+    default: Option<&'a Lit>,
+    file: bool,
+    skip: bool,
+    default_fn: Option<&'a Lit>,
+    flatten: bool,
+    prefix: Option<&'a Lit>,
+    rename: Option<&'a Lit>,
+    aliases: Vec<Alias<'a>>,
+    deprecated: Option<&'a Lit>,
+    secret: bool,
+    interpolate: bool,
+    help: Option<String>,
+    validators: Vec<Validator<'a>>,
+}
+
+/// A check of a field value after parsing, set by `range(min = 1, max = 10)`,
+/// `len(min = 1, max = 10)`, `regex = "..."`, `one_of("a", "b")` or `non_empty`.
+enum Validator<'a> {
+    Range {
+        min: Option<&'a Lit>,
+        max: Option<&'a Lit>,
+    },
+    Len {
+        min: Option<&'a Lit>,
+        max: Option<&'a Lit>,
+    },
+    Regex(&'a Lit),
+    OneOf(Vec<&'a Lit>),
+    NonEmpty,
+}
+
+/// A fallback name of a field variable, set by `alias = "NAME"` or
+/// `alias(name = "NAME", deprecated = "message")`.
+struct Alias<'a> {
+    name: &'a Lit,
+    deprecated: Option<&'a Lit>,
+}
+
+impl FieldAttrs<'_> {
+    /// Returns `false` if the field is computed by `skip` or `default_fn` instead of being loaded
+    /// from variables.
+    fn is_loaded(&self) -> bool {
+        !self.skip && self.default_fn.is_none()
     }
 }
 
-fn gen_field_assign(field: &Field) -> proc_macro2::TokenStream {
-    match fetch_yasec_attr_from_field(field) {
-        Some(attr) => {
-            let list = fetch_list_from_attr(field, attr);
-            let env_name = find_item_in_list(field, &list, "env");
-            let opt_default = find_item_in_list(field, &list, "default");
-            gen_field_assign_for_struct_type(field, env_name, opt_default)
+/// Options which make a field computed instead of being loaded from variables.
+const COMPUTED_OPTIONS: &[&str] = &["skip", "default_fn"];
+
+/// Options which make sense only for a field loaded from variables.
+const LOADED_OPTIONS: &[&str] = &[
+    "env",
+    "default",
+    "file",
+    "alias",
+    "deprecated",
+    "interpolate",
+    "range",
+    "len",
+    "regex",
+    "one_of",
+    "non_empty",
+];
+
+fn fetch_field_attrs<'a>(
+    field: &Field,
+    list: &'a Punctuated<NestedMeta, Comma>,
+) -> syn::Result<FieldAttrs<'a>> {
+    check_conflict(list, "flatten", "prefix")?;
+    check_conflict(list, "flatten", "rename")?;
+    check_conflict(list, "prefix", "rename")?;
+    check_conflict(list, "skip", "default_fn")?;
+    check_conflict(list, "secret", "interpolate")?;
+    for computed in COMPUTED_OPTIONS {
+        for loaded in LOADED_OPTIONS {
+            check_conflict(list, computed, loaded)?;
         }
-        None => gen_field_assign_for_struct_type(field, None, None),
     }
+    Ok(FieldAttrs {
+        env: find_item_in_list(list, "env"),
+        default: find_item_in_list(list, "default"),
+        file: has_flag_in_list(list, "file"),
+        skip: has_flag_in_list(list, "skip"),
+        default_fn: find_item_in_list(list, "default_fn"),
+        flatten: has_flag_in_list(list, "flatten"),
+        prefix: find_item_in_list(list, "prefix"),
+        rename: find_item_in_list(list, "rename"),
+        aliases: fetch_aliases(list)?,
+        deprecated: find_item_in_list(list, "deprecated"),
+        secret: has_flag_in_list(list, "secret"),
+        interpolate: has_flag_in_list(list, "interpolate"),
+        help: find_item_in_list(list, "help")
+            .map(lit_value)
+            .or_else(|| fetch_doc(&field.attrs)),
+        validators: fetch_validators(list)?,
+    })
+}
+
+/// Returns an error pointing to the second option if both options are set.
+fn check_conflict(
+    list: &Punctuated<NestedMeta, Comma>,
+    first: &str,
+    second: &str,
+) -> syn::Result<()> {
+    match (find_option(list, first), find_option(list, second)) {
+        (Some(_), Some(item)) => Err(syn::Error::new_spanned(
+            item,
+            format!("`{}` can't be combined with `{}`", second, first),
+        )),
+        _ => Ok(()),
+    }
+}
+
+fn fetch_rename_all(list: &Punctuated<NestedMeta, Comma>) -> syn::Result<Option<RenameRule>> {
+    find_item_in_list(list, "rename_all")
+        .map(|x| {
+            RenameRule::from_name(&lit_value(x))
+                .map_err(|message| syn::Error::new_spanned(x, message))
+        })
+        .transpose()
+}
+
+fn fetch_list_from_field(field: &Field) -> syn::Result<Punctuated<NestedMeta, Comma>> {
+    fetch_list_from_attrs(&field.attrs, FIELD_OPTIONS)
+}
+
+/// Name of a local variable that keeps a value of the field with the index.
+fn field_var(index: usize) -> Ident {
+    format_ident!("field_{}", index)
+}
+
+/// Generates a statement that initializes the field or collects its error, so every field is
+/// checked before returning.
+fn gen_field_assign(
+    index: usize,
+    field: &Field,
+    attrs: &FieldAttrs,
+    rename_all: RenameRule,
+) -> syn::Result<proc_macro2::TokenStream> {
+    let var = field_var(index);
+    if attrs.skip {
+        return Ok(quote! {
+            let #var = Some(::std::default::Default::default());
+        });
+    }
+    if let Some(default_fn) = attrs.default_fn {
+        let default_fn = parse_path(default_fn)?;
+        return Ok(quote! {
+            let #var = Some(#default_fn());
+        });
+    }
+    let path = field_type_path(field)?;
+    let context = gen_field_context(field, attrs, rename_all);
+    let checks = gen_field_checks(&path, &attrs.validators).map(|checks| {
+        quote! {
+            .and_then(|value| {
+                let context = #context;
+                #checks
+                Ok(value)
+            })
+        }
+    });
+    Ok(quote! {
+        let #var = match #path :: with_context(#context) #checks {
+            Ok(value) => Some(value),
+            Err(e) => {
+                errors.push(e);
+                None
+            }
+        };
+    })
 }
 
 // converts Option<T> to Option::<T>
@@ -87,147 +586,472 @@ fn norm_path(path: &mut syn::TypePath) {
     });
 }
 
-fn remove_quotes(val: &str) -> String {
-    val.chars().skip(1).take(val.len() - 2).collect()
+/// Returns the value of a string literal. Options are checked to be strings by `check_options`.
+fn lit_value(lit: &Lit) -> String {
+    match lit {
+        Lit::Str(lit) => lit.value(),
+        lit => to_s(lit),
+    }
 }
 
-fn gen_field_assign_for_struct_type(
-    field: &Field,
-    name: Option<&Lit>,
-    default: Option<&Lit>,
-) -> proc_macro2::TokenStream {
-    let ident = &field.ident;
-    let var_name = name.map(|x| remove_quotes(&to_s(x))).unwrap_or_default();
-    let var_type = to_s(&field.ty);
-    let ident_str = to_s(ident).to_uppercase();
+fn field_type_path(field: &Field) -> syn::Result<syn::TypePath> {
     match &field.ty {
         syn::Type::Path(path) => {
             let mut path = path.clone();
             norm_path(&mut path);
-            match default {
-                Some(_) => quote! {
-                    #ident: #path :: with_context(
-                        context
-                            .with_var_name(#var_name, #var_type)
-                            .push_prefix(#ident_str.to_owned())
-                            .with_default_value(#default)
-                    )?
-                },
-                None => quote! {
-                    #ident: #path :: with_context(
-                        context
-                            .with_var_name(#var_name, #var_type)
-                            .push_prefix(#ident_str.to_owned())
-                    )?
-                },
-            }
+            Ok(path)
         }
-        _ => panic!("Expected field type to be a path: {:?}", ident),
+        ty => Err(syn::Error::new_spanned(
+            ty,
+            "yasec supports only fields of path types like `u16` or `Option<String>`",
+        )),
     }
 }
 
-fn gen_field_usage(field: &Field) -> proc_macro2::TokenStream {
-    match fetch_yasec_attr_from_field(field) {
-        Some(attr) => {
-            let list = fetch_list_from_attr(field, attr);
-            let from_value = find_item_in_list(field, &list, "from");
-            let opt_default = find_item_in_list(field, &list, "default");
-            gen_field_usage_for_struct_type(field, from_value, opt_default)
-        }
-        None => gen_field_usage_for_struct_type(field, None, None),
+/// Parses a path like `defaults::port` from the string literal.
+fn parse_path(lit: &Lit) -> syn::Result<syn::ExprPath> {
+    match lit {
+        Lit::Str(path) => path.parse().ok(),
+        _ => None,
     }
+    .ok_or_else(|| syn::Error::new_spanned(lit, "expected a path like `defaults::port`"))
 }
 
-fn gen_field_usage_for_struct_type(
+/// Generates an expression that builds the context of the field from the parent `context`.
+fn gen_field_context(
     field: &Field,
-    name: Option<&Lit>,
-    default: Option<&Lit>,
+    attrs: &FieldAttrs,
+    rename_all: RenameRule,
 ) -> proc_macro2::TokenStream {
-    let ident = &field.ident;
-    let var_name = name.map(|x| remove_quotes(&to_s(x))).unwrap_or_default();
+    let var_name = attrs.env.map(lit_value).unwrap_or_default();
     let var_type = to_s(&field.ty);
-    let ident_str = to_s(ident).to_uppercase();
-    match &field.ty {
-        syn::Type::Path(path) => {
-            let mut path = path.clone();
-            norm_path(&mut path);
-            match default {
-                Some(_) => quote! {
-                    #path :: usage_with_context(
-                        context
-                            .with_var_name(#var_name, #var_type)
-                            .push_prefix(#ident_str.to_owned())
-                            .with_default_value(#default)
-                    )?
-                },
-                None => quote! {
-                    #path :: usage_with_context(
-                        context
-                            .with_var_name(#var_name, #var_type)
-                            .push_prefix(#ident_str.to_owned())
-                    )?
-                },
-            }
-        }
-        _ => panic!("Expected field type to be a path: {:?}", ident),
-    }
-}
-
-fn fetch_yasec_attr_from_field(field: &Field) -> Option<&Attribute> {
-    field.attrs.iter().find(|a| {
-        let path = &a.path;
-        let name = quote!(#path).to_string();
-        name == "yasec"
-    })
+    let ident_str = match attrs.rename.or(attrs.prefix) {
+        Some(name) => lit_value(name),
+        None => rename_all.apply(&field.ident.as_ref().unwrap().unraw().to_string()),
+    };
+    let prefix = (!attrs.flatten).then(|| quote!(.push_prefix(#ident_str.to_owned())));
+    let default = attrs.default.map(|x| quote!(.with_default_value(#x)));
+    let file = attrs.file.then(|| quote!(.with_file_suffix()));
+    let alias_names = attrs.aliases.iter().map(|alias| alias.name);
+    let aliases = (!attrs.aliases.is_empty()).then(|| quote!(.with_aliases(&[#(#alias_names),*])));
+    let deprecated_aliases = attrs.aliases.iter().filter_map(|alias| {
+        let name = alias.name;
+        alias
+            .deprecated
+            .map(|message| quote!(.with_deprecated_name(#name, #message)))
+    });
+    let deprecated = attrs.deprecated.map(|x| quote!(.with_deprecated(#x)));
+    let secret = attrs.secret.then(|| quote!(.with_secret()));
+    let interpolation = attrs.interpolate.then(|| quote!(.with_interpolation()));
+    let help = attrs.help.as_ref().map(|x| quote!(.with_description(#x)));
+    let constraints = attrs.validators.iter().map(gen_validator_constraint);
+    quote! {
+        context
+            .with_var_name(#var_name, #var_type)
+            #prefix
+            #default
+            #file
+            #aliases
+            #(#deprecated_aliases)*
+            #deprecated
+            #secret
+            #interpolation
+            #help
+            #(#constraints)*
+    }
 }
 
-fn fetch_list_from_attr(field: &Field, attr: &Attribute) -> Punctuated<NestedMeta, Comma> {
-    let opt_meta = attr.parse_meta().unwrap_or_else(|e| {
-        panic!(
-            "Can not interpret meta of `yasec` attribute on field `{}`, {}",
-            field_name(field),
-            e
-        )
+/// Generates statements which validate `value` of the field. A value of `Option` is validated
+/// if it's set.
+fn gen_field_checks(
+    path: &syn::TypePath,
+    validators: &[Validator],
+) -> Option<proc_macro2::TokenStream> {
+    if validators.is_empty() {
+        return None;
+    }
+    let calls = validators.iter().map(|validator| match validator {
+        Validator::Range { min, max } => {
+            let (min, max) = (gen_option(*min), gen_option(*max));
+            quote!(::yasec::check_range(&context, checked, #min, #max)?;)
+        }
+        Validator::Len { min, max } => {
+            let (min, max) = (gen_option(*min), gen_option(*max));
+            quote!(::yasec::check_len(&context, checked, #min, #max)?;)
+        }
+        Validator::Regex(pattern) => quote!(::yasec::check_regex(&context, checked, #pattern)?;),
+        Validator::OneOf(allowed) => {
+            quote!(::yasec::check_one_of(&context, checked, &[#(#allowed),*])?;)
+        }
+        Validator::NonEmpty => quote!(::yasec::check_non_empty(&context, checked)?;),
     });
+    let is_option = path
+        .path
+        .segments
+        .last()
+        .is_some_and(|segment| segment.ident == "Option");
+    Some(match is_option {
+        true => quote! {
+            if let Some(checked) = &value {
+                #(#calls)*
+            }
+        },
+        false => quote! {
+            let checked = &value;
+            #(#calls)*
+        },
+    })
+}
 
-    match opt_meta {
-        Meta::List(l) => l.nested,
-        _ => panic!(
-            "`yasec` attribute on field `{}` must contain a list",
-            field_name(field)
-        ),
+/// Generates an expression that adds the text of the validator to the context for usage.
+fn gen_validator_constraint(validator: &Validator) -> proc_macro2::TokenStream {
+    match validator {
+        Validator::Range { min, max } => {
+            let (min, max) = (gen_option(*min), gen_option(*max));
+            quote!(.with_constraint(&::yasec::format_bounds(&#min, &#max)))
+        }
+        Validator::Len { min, max } => {
+            let (min, max) = (gen_option(*min), gen_option(*max));
+            quote!(.with_constraint(&format!("length {}", ::yasec::format_bounds::<usize>(&#min, &#max))))
+        }
+        Validator::Regex(pattern) => quote!(.with_constraint(&format!("matches {}", #pattern))),
+        Validator::OneOf(allowed) => {
+            let allowed = allowed
+                .iter()
+                .map(|x| lit_value(x))
+                .collect::<Vec<_>>()
+                .join(", ");
+            quote!(.with_constraint(concat!("one of: ", #allowed)))
+        }
+        Validator::NonEmpty => quote!(.with_constraint("non-empty")),
+    }
+}
+
+fn gen_option(lit: Option<&Lit>) -> proc_macro2::TokenStream {
+    match lit {
+        Some(lit) => quote!(Some(#lit)),
+        None => quote!(None),
     }
 }
 
-fn find_item_in_list<'l, 'n>(
+fn gen_field_usage(
     field: &Field,
+    attrs: &FieldAttrs,
+    rename_all: RenameRule,
+) -> syn::Result<proc_macro2::TokenStream> {
+    let path = field_type_path(field)?;
+    let context = gen_field_context(field, attrs, rename_all);
+    Ok(quote! {
+        #path :: usage_with_context(#context)?
+    })
+}
+
+/// Joins lines of `///` doc comments into one line.
+fn fetch_doc(attrs: &[Attribute]) -> Option<String> {
+    let lines = attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident("doc"))
+        .filter_map(|attr| match attr.parse_meta() {
+            Ok(Meta::NameValue(syn::MetaNameValue {
+                lit: Lit::Str(lit), ..
+            })) => Some(lit.value().trim().to_owned()),
+            _ => None,
+        })
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<_>>();
+    (!lines.is_empty()).then(|| lines.join(" "))
+}
+
+/// Shape of an option of `yasec` attribute.
+#[derive(Clone, Copy, PartialEq)]
+enum Shape {
+    /// `name`
+    Flag,
+    /// `name = "value"`
+    Str,
+    /// `name = 10`
+    Num,
+    /// `name(...)`
+    List,
+}
+
+const STRUCT_OPTIONS: &[(&str, Shape)] = &[
+    ("prefix", Shape::Str),
+    ("rename_all", Shape::Str),
+    ("separator", Shape::Str),
+    ("validate", Shape::Str),
+    ("interpolate", Shape::Flag),
+];
+
+const ENUM_OPTIONS: &[(&str, Shape)] = &[
+    ("case_insensitive", Shape::Flag),
+    ("rename_all", Shape::Str),
+    ("tag", Shape::Str),
+];
+
+const VARIANT_OPTIONS: &[(&str, Shape)] = &[("rename", Shape::Str), ("alias", Shape::Str)];
+
+const FIELD_OPTIONS: &[(&str, Shape)] = &[
+    ("env", Shape::Str),
+    ("default", Shape::Str),
+    ("file", Shape::Flag),
+    ("skip", Shape::Flag),
+    ("default_fn", Shape::Str),
+    ("flatten", Shape::Flag),
+    ("prefix", Shape::Str),
+    ("rename", Shape::Str),
+    ("alias", Shape::Str),
+    ("alias", Shape::List),
+    ("deprecated", Shape::Str),
+    ("secret", Shape::Flag),
+    ("interpolate", Shape::Flag),
+    ("help", Shape::Str),
+    ("range", Shape::List),
+    ("len", Shape::List),
+    ("regex", Shape::Str),
+    ("one_of", Shape::List),
+    ("non_empty", Shape::Flag),
+];
+
+const ALIAS_OPTIONS: &[(&str, Shape)] = &[("name", Shape::Str), ("deprecated", Shape::Str)];
+
+const BOUNDS_OPTIONS: &[(&str, Shape)] = &[("min", Shape::Num), ("max", Shape::Num)];
+
+/// Returns items of all `yasec` attributes or an empty list if there is no attribute. Every item
+/// must be one of the options, which may be set once except `alias`.
+fn fetch_list_from_attrs(
+    attrs: &[Attribute],
+    options: &[(&str, Shape)],
+) -> syn::Result<Punctuated<NestedMeta, Comma>> {
+    let mut list = Punctuated::new();
+    for attr in attrs.iter().filter(|attr| attr.path.is_ident("yasec")) {
+        match attr.parse_meta()? {
+            Meta::List(l) => list.extend(l.nested),
+            meta => {
+                return Err(syn::Error::new_spanned(
+                    meta,
+                    "expected a list of options like `#[yasec(...)]`",
+                ))
+            }
+        }
+    }
+    check_options(&list, options)?;
+    Ok(list)
+}
+
+fn check_options(
+    list: &Punctuated<NestedMeta, Comma>,
+    options: &[(&str, Shape)],
+) -> syn::Result<()> {
+    let mut seen = vec![];
+    for item in list {
+        let path = match item {
+            NestedMeta::Meta(meta) => meta.path(),
+            NestedMeta::Lit(lit) => {
+                return Err(syn::Error::new_spanned(
+                    lit,
+                    "expected an option like `name` or `name = \"value\"`",
+                ))
+            }
+        };
+        let name = to_s(path);
+        let shapes = options
+            .iter()
+            .filter(|(option, _)| *option == name)
+            .map(|(_, shape)| *shape)
+            .collect::<Vec<_>>();
+        if shapes.is_empty() {
+            return Err(unknown_option(path, &name, options));
+        }
+        if !item_shape(item).is_some_and(|shape| shapes.contains(&shape)) {
+            let expected = shapes
+                .iter()
+                .map(|shape| describe_option(&name, *shape))
+                .collect::<Vec<_>>()
+                .join(" or ");
+            return Err(syn::Error::new_spanned(
+                item,
+                format!("expected {}", expected),
+            ));
+        }
+        if name != "alias" && seen.contains(&name) {
+            return Err(syn::Error::new_spanned(
+                item,
+                format!("duplicate option `{}`", name),
+            ));
+        }
+        seen.push(name);
+    }
+    Ok(())
+}
+
+/// Builds an error of an unknown option suggesting the closest known one, e.g. `default` for
+/// `defualt`.
+fn unknown_option(path: &syn::Path, name: &str, options: &[(&str, Shape)]) -> syn::Error {
+    let mut known = options
+        .iter()
+        .map(|(option, _)| *option)
+        .collect::<Vec<_>>();
+    known.dedup();
+    let closest = known
+        .iter()
+        .map(|option| (strsim::jaro_winkler(name, option), option))
+        .filter(|(similarity, _)| *similarity >= 0.8)
+        .max_by(|a, b| a.0.total_cmp(&b.0));
+    let message = match closest {
+        Some((_, option)) => format!("unknown option `{}`, did you mean `{}`?", name, option),
+        None if known.is_empty() => "`yasec` options aren't supported here".to_owned(),
+        None => format!(
+            "unknown option `{}`, expected one of: {}",
+            name,
+            known.join(", ")
+        ),
+    };
+    syn::Error::new_spanned(path, message)
+}
+
+fn item_shape(item: &NestedMeta) -> Option<Shape> {
+    match item {
+        NestedMeta::Meta(Meta::Path(_)) => Some(Shape::Flag),
+        NestedMeta::Meta(Meta::List(_)) => Some(Shape::List),
+        NestedMeta::Meta(Meta::NameValue(name_value)) => match name_value.lit {
+            Lit::Str(_) => Some(Shape::Str),
+            Lit::Int(_) | Lit::Float(_) => Some(Shape::Num),
+            _ => None,
+        },
+        NestedMeta::Lit(_) => None,
+    }
+}
+
+fn describe_option(name: &str, shape: Shape) -> String {
+    match shape {
+        Shape::Flag => format!("`{}` without a value", name),
+        Shape::Str => format!("`{} = \"...\"`", name),
+        Shape::Num => format!("`{} = <number>`", name),
+        Shape::List => format!("`{}(...)`", name),
+    }
+}
+
+fn find_option<'l>(list: &'l Punctuated<NestedMeta, Comma>, name: &str) -> Option<&'l NestedMeta> {
+    list.iter().find(|item| match item {
+        NestedMeta::Meta(meta) => meta.path().is_ident(name),
+        NestedMeta::Lit(_) => false,
+    })
+}
+
+fn find_item_in_list<'l>(
     list: &'l Punctuated<NestedMeta, Comma>,
-    item_name: &'n str,
+    item_name: &str,
 ) -> Option<&'l Lit> {
+    find_items_in_list(list, item_name).into_iter().next()
+}
+
+fn find_items_in_list<'l>(
+    list: &'l Punctuated<NestedMeta, Comma>,
+    item_name: &str,
+) -> Vec<&'l Lit> {
     list.iter()
-        .map(|item| match item {
-            NestedMeta::Meta(meta) => match meta {
-                Meta::NameValue(name_value) => name_value,
-                _ => panic!(
-                    "`yasec` attribute on field `{}` must contain name/value item",
-                    field_name(field)
-                ),
-            },
-            _ => panic!(
-                "Failed to process `yasec` attribute on field `{}`",
-                field_name(field)
-            ),
-        })
-        .find(|name_value| {
-            let ident = &name_value.path;
-            let name = quote!(#ident).to_string();
-            name == item_name
+        .filter_map(|item| match item {
+            NestedMeta::Meta(Meta::NameValue(name_value))
+                if name_value.path.is_ident(item_name) =>
+            {
+                Some(&name_value.lit)
+            }
+            _ => None,
         })
-        .map(|item| &item.lit)
+        .collect()
 }
 
-fn field_name(field: &Field) -> String {
-    to_s(&field.ident)
+fn fetch_aliases(list: &Punctuated<NestedMeta, Comma>) -> syn::Result<Vec<Alias<'_>>> {
+    let mut aliases = vec![];
+    for item in list {
+        match item {
+            NestedMeta::Meta(Meta::NameValue(name_value)) if name_value.path.is_ident("alias") => {
+                aliases.push(Alias {
+                    name: &name_value.lit,
+                    deprecated: None,
+                })
+            }
+            NestedMeta::Meta(Meta::List(alias)) if alias.path.is_ident("alias") => {
+                check_options(&alias.nested, ALIAS_OPTIONS)?;
+                let name = find_item_in_list(&alias.nested, "name").ok_or_else(|| {
+                    syn::Error::new_spanned(alias, "`alias(...)` must contain `name = \"...\"`")
+                })?;
+                aliases.push(Alias {
+                    name,
+                    deprecated: find_item_in_list(&alias.nested, "deprecated"),
+                })
+            }
+            _ => (),
+        }
+    }
+    Ok(aliases)
+}
+
+fn fetch_validators(list: &Punctuated<NestedMeta, Comma>) -> syn::Result<Vec<Validator<'_>>> {
+    let mut validators = vec![];
+    for item in list {
+        match item {
+            NestedMeta::Meta(Meta::List(bounds))
+                if bounds.path.is_ident("range") || bounds.path.is_ident("len") =>
+            {
+                check_options(&bounds.nested, BOUNDS_OPTIONS)?;
+                let min = find_item_in_list(&bounds.nested, "min");
+                let max = find_item_in_list(&bounds.nested, "max");
+                if min.is_none() && max.is_none() {
+                    return Err(syn::Error::new_spanned(
+                        bounds,
+                        format!("`{}(...)` must contain `min` or `max`", to_s(&bounds.path)),
+                    ));
+                }
+                validators.push(match bounds.path.is_ident("range") {
+                    true => Validator::Range { min, max },
+                    false => Validator::Len { min, max },
+                });
+            }
+            NestedMeta::Meta(Meta::List(one_of)) if one_of.path.is_ident("one_of") => {
+                let allowed = one_of
+                    .nested
+                    .iter()
+                    .map(|item| match item {
+                        NestedMeta::Lit(lit @ Lit::Str(_)) => Ok(lit),
+                        _ => Err(syn::Error::new_spanned(item, "expected a string")),
+                    })
+                    .collect::<syn::Result<Vec<_>>>()?;
+                if allowed.is_empty() {
+                    return Err(syn::Error::new_spanned(
+                        one_of,
+                        "`one_of(...)` must contain at least one value",
+                    ));
+                }
+                validators.push(Validator::OneOf(allowed));
+            }
+            NestedMeta::Meta(Meta::NameValue(regex)) if regex.path.is_ident("regex") => {
+                if let Lit::Str(pattern) = &regex.lit {
+                    regex::Regex::new(&pattern.value()).map_err(|e| {
+                        // Syntax errors draw the pattern over several lines, the reason is the last one.
+                        let e = e.to_string();
+                        let reason = e.lines().last().unwrap_or_default().trim();
+                        let reason = reason.strip_prefix("error: ").unwrap_or(reason);
+                        syn::Error::new_spanned(pattern, format!("invalid pattern, {}", reason))
+                    })?;
+                }
+                validators.push(Validator::Regex(&regex.lit));
+            }
+            NestedMeta::Meta(Meta::Path(path)) if path.is_ident("non_empty") => {
+                validators.push(Validator::NonEmpty);
+            }
+            _ => (),
+        }
+    }
+    Ok(validators)
+}
+
+fn has_flag_in_list(list: &Punctuated<NestedMeta, Comma>, flag_name: &str) -> bool {
+    list.iter().any(|item| match item {
+        NestedMeta::Meta(Meta::Path(path)) => path.is_ident(flag_name),
+        _ => false,
+    })
 }
 
 fn to_s<T: quote::ToTokens>(node: &T) -> String {